@@ -0,0 +1,66 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Times [GameState::check_with] for the standard fleet and for a large custom fleet, for which
+//! overlaps are detected with a bitboard. Run with `cargo bench -p battleship-core`.
+
+use std::{hint::black_box, time::Instant};
+
+use battleship_core::{Direction, Fleet, GameConfig, GameState, Ship, ShipClass};
+
+const ITERATIONS: u32 = 10_000;
+
+fn bench(name: &str, state: &GameState, config: &GameConfig) {
+    assert!(state.check_with(config));
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(black_box(state).check_with(black_box(config)));
+    }
+    println!("{}: {:.2?} per check", name, start.elapsed() / ITERATIONS);
+}
+
+fn main() {
+    let standard = GameState {
+        ships: vec![
+            Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+            Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
+            Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
+            Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+            Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+        ],
+        ..GameState::new([0x42; 32])
+    };
+    bench("standard fleet", &standard, &GameConfig::default());
+
+    // Fifty destroyers, filling every cell of the board.
+    let large = GameState {
+        ships: (0..50)
+            .map(|i| {
+                Ship::new(
+                    ShipClass::Destroyer,
+                    (i % 10, i / 10 * 2),
+                    Direction::Vertical,
+                )
+            })
+            .collect(),
+        ..GameState::new([0x42; 32])
+    };
+    let config = GameConfig {
+        fleet: Fleet {
+            classes: vec![ShipClass::Destroyer; 50],
+        },
+        ..Default::default()
+    };
+    bench("fifty destroyers", &large, &config);
+}
@@ -0,0 +1,366 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    fog::surviving_candidates, Fleet, FogBoard, GameState, HitType, Knowledge, Position, Ship,
+    ShipClass, BOARD_SIZE,
+};
+
+/// Extra weight given to a placement for each known hit it covers, so that cells next to hits are
+/// targeted before searching the rest of the board.
+const HIT_WEIGHT: u32 = 20;
+
+/// Per-cell placement counts, indexed as `density[y][x]`.
+pub type Density = [[u32; BOARD_SIZE]; BOARD_SIZE];
+
+/// Computes the probability density of ship locations on the fog board.
+///
+/// Each placement of a class that may still be afloat, and that covers no miss and no sunk cell,
+/// adds its weight to every cell it covers. The weight is one, plus [HIT_WEIGHT] for each hit it
+/// covers, counted once for each ship of the class still afloat.
+pub fn placement_density(fog: &FogBoard, fleet: &Fleet) -> Density {
+    let afloat = afloat_counts(fog, fleet);
+    let mut density = [[0; BOARD_SIZE]; BOARD_SIZE];
+    for class in ShipClass::list() {
+        for ship in class.valid_placements().filter(|ship| is_open(fog, ship)) {
+            add_weight(
+                &mut density,
+                &ship,
+                afloat[class.index()] * weight(fog, &ship),
+            );
+        }
+    }
+    density
+}
+
+/// Returns every cell of an empty square board of the given size, ordered from the best first shot
+/// to the worst, by the number of horizontal and vertical placements of the fleet covering it.
+///
+/// On the standard board this matches the order of [placement_density] before any shot, and so
+/// depends only on the fleet rather than any particular board. Ties are ordered row-major.
+pub fn opening_shots(fleet: &Fleet, board_size: usize) -> Vec<Position> {
+    // Placements along a single row or column of the board covering the given offset.
+    let covering = |offset: usize, span: usize| -> usize {
+        let first = (offset + 1).saturating_sub(span);
+        let last = offset.min(board_size.saturating_sub(span));
+        (last + 1).saturating_sub(first)
+    };
+    let density = |pos: &Position| -> usize {
+        let (x, y) = (pos.x as usize, pos.y as usize);
+        fleet
+            .classes
+            .iter()
+            .map(|class| covering(x, class.span() as usize) + covering(y, class.span() as usize))
+            .sum()
+    };
+
+    let mut cells: Vec<Position> = (0..board_size * board_size)
+        .map(|i| Position::from(((i % board_size) as u32, (i / board_size) as u32)))
+        .collect();
+    cells.sort_by_key(|pos| std::cmp::Reverse(density(pos)));
+    cells
+}
+
+/// A computer player that shoots at the cell with the highest [placement_density].
+///
+/// The density is maintained incrementally as results are recorded, updating only the placements
+/// that cover the cell that was shot, rather than recounting every placement on each turn. The
+/// player uses no randomness, so the same results always lead to the same shots and self-play is
+/// reproducible.
+#[derive(Clone, Debug)]
+pub struct AiPlayer {
+    fog: FogBoard,
+    /// Placements covering no miss and no sunk cell, for each class in the order of
+    /// [ShipClass::list].
+    placements: Vec<Vec<Ship>>,
+    /// Number of ships of each class that may still be afloat.
+    afloat: Vec<u32>,
+    density: Density,
+}
+
+impl AiPlayer {
+    pub fn new(fleet: &Fleet) -> Self {
+        let fog = FogBoard::new();
+        let afloat = afloat_counts(&fog, fleet);
+        Self {
+            // Classes not in the fleet have no placements to track.
+            placements: ShipClass::list()
+                .iter()
+                .map(|class| match afloat[class.index()] {
+                    0 => Vec::new(),
+                    _ => class.valid_placements().collect(),
+                })
+                .collect(),
+            afloat,
+            density: placement_density(&fog, fleet),
+            fog,
+        }
+    }
+
+    /// What the player has observed of the opponent's board.
+    pub fn fog(&self) -> &FogBoard {
+        &self.fog
+    }
+
+    pub fn density(&self) -> &Density {
+        &self.density
+    }
+
+    /// Chooses the unshot cell with the highest density, preferring the first in the order of
+    /// [Position::all] on ties. Returns `None` if every cell has been shot.
+    pub fn next_shot(&self) -> Option<Position> {
+        Position::all()
+            .enumerate()
+            .filter(|&(_, pos)| self.fog.get(pos) == Some(Knowledge::Unknown))
+            .max_by_key(|&(index, pos)| {
+                // Reverse the index so that max_by_key, which keeps the last maximum, keeps the
+                // first position instead.
+                (
+                    self.density[pos.y as usize][pos.x as usize],
+                    usize::MAX - index,
+                )
+            })
+            .map(|(_, pos)| pos)
+    }
+
+    /// Records the result of a shot, updating the density. Results for cells that are already
+    /// known, and invalid or deflected shots, are ignored.
+    pub fn record(&mut self, shot: Position, hit: &HitType) {
+        if self.fog.get(shot) != Some(Knowledge::Unknown) {
+            return;
+        }
+        match hit {
+            HitType::Miss => self.remove_covering(shot),
+            HitType::Hit => {
+                for (i, placements) in self.placements.iter().enumerate() {
+                    for ship in placements.iter().filter(|ship| covers(ship, shot)) {
+                        add_weight(&mut self.density, ship, self.afloat[i] * HIT_WEIGHT);
+                    }
+                }
+            }
+            HitType::Sunk(class) => {
+                self.remove_covering(shot);
+                // One fewer ship of the class is afloat, so its placements each count once less.
+                let i = class.index();
+                self.afloat[i] = self.afloat[i].saturating_sub(1);
+                for ship in self.placements[i].iter() {
+                    sub_weight(&mut self.density, ship, weight(&self.fog, ship));
+                }
+                if self.afloat[i] == 0 {
+                    self.placements[i].clear();
+                }
+            }
+            HitType::Invalid | HitType::Deflected => return,
+        }
+        self.fog.record(shot, hit);
+    }
+
+    /// Drops the placements covering the given cell, which can no longer hold an afloat ship.
+    fn remove_covering(&mut self, shot: Position) {
+        for (i, placements) in self.placements.iter_mut().enumerate() {
+            placements.retain(|ship| {
+                if !covers(ship, shot) {
+                    return true;
+                }
+                sub_weight(
+                    &mut self.density,
+                    ship,
+                    self.afloat[i] * weight(&self.fog, ship),
+                );
+                false
+            });
+        }
+    }
+}
+
+impl GameState {
+    /// Rates how hard the board is to clear, as the number of shots an [AiPlayer] takes to sink
+    /// every ship, starting from a board with no hits. The player is deterministic, so a single
+    /// game gives its expected number of shots.
+    pub fn difficulty(&self) -> f64 {
+        let mut state = self.clone();
+        for ship in state.ships.iter_mut() {
+            ship.hit_mask = 0;
+        }
+        let fleet = Fleet {
+            classes: state.ships.iter().map(|ship| ship.class).collect(),
+        };
+
+        let mut ai = AiPlayer::new(&fleet);
+        let mut shots = 0;
+        while !state.is_defeated() {
+            let Some(shot) = ai.next_shot() else {
+                break;
+            };
+            ai.record(shot, &state.apply_shot(shot));
+            shots += 1;
+        }
+        shots as f64
+    }
+}
+
+fn afloat_counts(fog: &FogBoard, fleet: &Fleet) -> Vec<u32> {
+    let surviving = surviving_candidates(fog, fleet);
+    ShipClass::list()
+        .iter()
+        .map(|class| surviving.iter().filter(|c| *c == class).count() as u32)
+        .collect()
+}
+
+fn covers(ship: &Ship, pos: Position) -> bool {
+    ship.points().any(|p| p == pos)
+}
+
+fn is_open(fog: &FogBoard, ship: &Ship) -> bool {
+    ship.points().all(|pos| {
+        !matches!(
+            fog.get(pos),
+            Some(Knowledge::Miss) | Some(Knowledge::Sunk(_))
+        )
+    })
+}
+
+fn weight(fog: &FogBoard, ship: &Ship) -> u32 {
+    let hits = ship
+        .points()
+        .filter(|&pos| fog.get(pos) == Some(Knowledge::Hit))
+        .count() as u32;
+    1 + HIT_WEIGHT * hits
+}
+
+fn add_weight(density: &mut Density, ship: &Ship, weight: u32) {
+    for pos in ship.points() {
+        density[pos.y as usize][pos.x as usize] += weight;
+    }
+}
+
+fn sub_weight(density: &mut Density, ship: &Ship, weight: u32) {
+    for pos in ship.points() {
+        density[pos.y as usize][pos.x as usize] -= weight;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::example_state, Direction, GameState};
+
+    #[test]
+    fn cached_density_matches_recomputed() {
+        let mut state = example_state();
+        let fleet = Fleet::standard();
+        let mut ai = AiPlayer::new(&fleet);
+
+        // Start with a few fixed shots, including a sinking, then let the AI finish the game.
+        let opening = [(7, 7), (0, 0), (8, 7), (2, 4), (2, 4)].map(Position::from);
+        let mut shots = 0;
+        while !state.is_defeated() {
+            let shot = match opening.get(shots) {
+                Some(&shot) => shot,
+                None => ai.next_shot().unwrap(),
+            };
+            ai.record(shot, &state.apply_shot(shot));
+            assert_eq!(ai.density(), &placement_density(ai.fog(), &fleet));
+            shots += 1;
+        }
+        assert!(shots < BOARD_SIZE * BOARD_SIZE);
+    }
+
+    #[test]
+    fn opening_shots_are_central() {
+        let fleet = Fleet::standard();
+        let openings = opening_shots(&fleet, BOARD_SIZE);
+        assert_eq!(openings.len(), BOARD_SIZE * BOARD_SIZE);
+
+        // The four central cells come first, and the corners last.
+        let central = Position::range((4, 4), (5, 5)).collect::<Vec<_>>();
+        assert_eq!(openings[..4], central);
+        for corner in [(0, 0), (9, 0), (0, 9), (9, 9)].map(Position::from) {
+            assert!(openings[96..].contains(&corner));
+        }
+
+        // The order follows the density of an AI that has not shot yet.
+        let density = placement_density(&FogBoard::new(), &fleet);
+        let at = |pos: &Position| density[pos.y as usize][pos.x as usize];
+        assert!(openings.windows(2).all(|w| at(&w[0]) >= at(&w[1])));
+        assert_eq!(AiPlayer::new(&fleet).next_shot(), Some(openings[0]));
+    }
+
+    #[test]
+    fn difficulty() {
+        let spread = example_state();
+        let corner = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Carrier, (0, 0), Direction::Horizontal),
+                Ship::new(ShipClass::Battleship, (0, 1), Direction::Horizontal),
+                Ship::new(ShipClass::Cruiser, (0, 2), Direction::Horizontal),
+                Ship::new(ShipClass::Submarine, (0, 3), Direction::Horizontal),
+                Ship::new(ShipClass::Destroyer, (0, 4), Direction::Horizontal),
+            ],
+            ..GameState::new(rand::random())
+        };
+
+        let (spread_difficulty, corner_difficulty) = (spread.difficulty(), corner.difficulty());
+        assert_ne!(spread_difficulty, corner_difficulty);
+        for difficulty in [spread_difficulty, corner_difficulty] {
+            assert!((17.0..=100.0).contains(&difficulty));
+        }
+
+        // Hits already on the board do not make it easier.
+        let mut damaged = spread.clone();
+        damaged.apply_shot((2, 3));
+        assert_eq!(damaged.difficulty(), spread_difficulty);
+    }
+
+    #[test]
+    fn targets_around_hit() {
+        let mut ai = AiPlayer::new(&Fleet::standard());
+        ai.record((5, 5).into(), &HitType::Hit);
+        let next = ai.next_shot().unwrap();
+        let (dx, dy) = (next.x.abs_diff(5), next.y.abs_diff(5));
+        assert_eq!(dx + dy, 1, "expected a neighbor of the hit, got {}", next);
+    }
+
+    #[test]
+    fn same_fog_same_shot() {
+        // The four central cells tie on an empty board, and the first of them is chosen.
+        let fleet = Fleet::standard();
+        let ai = AiPlayer::new(&fleet);
+        let density = placement_density(ai.fog(), &fleet);
+        for pos in [(4, 4), (5, 4), (4, 5), (5, 5)] {
+            assert_eq!(density[pos.1][pos.0], density[4][4]);
+        }
+        assert_eq!(ai.next_shot(), Some(Position { x: 4, y: 4 }));
+
+        // Recording the same results in a different order gives the same fog view, and so the
+        // same next shot, with no randomness involved.
+        let results = [
+            ((7, 7), HitType::Hit),
+            ((0, 0), HitType::Miss),
+            ((8, 7), HitType::Sunk(ShipClass::Destroyer)),
+            ((2, 4), HitType::Hit),
+            ((6, 2), HitType::Miss),
+        ];
+        let mut forward = AiPlayer::new(&fleet);
+        let mut backward = AiPlayer::new(&fleet);
+        for (shot, hit) in results.iter() {
+            forward.record((*shot).into(), hit);
+        }
+        for (shot, hit) in results.iter().rev() {
+            backward.record((*shot).into(), hit);
+        }
+        assert_eq!(forward.density(), backward.density());
+        assert_eq!(forward.next_shot(), backward.next_shot());
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Direction, GameError, GameState, Position, Ship, ShipClass, PEPPER_LEN};
+
+/// Builder for a [GameState], which validates the board when it is built.
+#[derive(Clone, Debug, Default)]
+pub struct GameStateBuilder {
+    ships: Vec<Ship>,
+    pepper: [u8; PEPPER_LEN],
+}
+
+impl GameStateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a ship of the given class at the given position and direction.
+    pub fn ship(mut self, class: ShipClass, pos: impl Into<Position>, dir: Direction) -> Self {
+        self.ships.push(Ship::new(class, pos, dir));
+        self
+    }
+
+    /// Sets the pepper. It defaults to all zeroes, which does not hide the board, so one of
+    /// [GameStateBuilder::pepper] or [GameStateBuilder::random_pepper] should be used.
+    pub fn pepper(mut self, pepper: [u8; PEPPER_LEN]) -> Self {
+        self.pepper = pepper;
+        self
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn random_pepper(self) -> Self {
+        self.pepper(rand::random())
+    }
+
+    /// Builds the state, checking that it is valid under the default rules as in
+    /// [GameState::check].
+    pub fn build(self) -> Result<GameState, GameError> {
+        GameState::with_ships(self.ships, self.pepper)
+    }
+}
+
+impl GameState {
+    pub fn builder() -> GameStateBuilder {
+        GameStateBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::example_state;
+
+    #[test]
+    fn build_basic_board() {
+        let pepper = rand::random();
+        let state = GameState::builder()
+            .ship(ShipClass::Carrier, (2, 3), Direction::Vertical)
+            .ship(ShipClass::Battleship, (3, 1), Direction::Horizontal)
+            .ship(ShipClass::Cruiser, (4, 7), Direction::Vertical)
+            .ship(ShipClass::Submarine, (7, 5), Direction::Horizontal)
+            .ship(ShipClass::Destroyer, (7, 7), Direction::Horizontal)
+            .pepper(pepper)
+            .build()
+            .unwrap();
+
+        let expected = GameState {
+            pepper: pepper.to_vec(),
+            ..example_state()
+        };
+        assert_eq!(state, expected);
+        assert!(state.check());
+    }
+
+    #[test]
+    fn build_invalid_board() {
+        let partial = GameState::builder()
+            .ship(ShipClass::Carrier, (2, 3), Direction::Vertical)
+            .ship(ShipClass::Battleship, (3, 1), Direction::Horizontal)
+            .ship(ShipClass::Cruiser, (4, 7), Direction::Vertical)
+            .ship(ShipClass::Submarine, (7, 5), Direction::Horizontal);
+        assert_eq!(
+            partial.clone().build(),
+            Err(GameError::MissingClass(ShipClass::Destroyer))
+        );
+        assert_eq!(
+            partial
+                .clone()
+                .ship(ShipClass::Destroyer, (2, 7), Direction::Horizontal)
+                .build(),
+            Err(GameError::Overlap(ShipClass::Carrier, ShipClass::Destroyer))
+        );
+        assert_eq!(
+            partial
+                .ship(ShipClass::Destroyer, (0, 0), Direction::DiagonalDown)
+                .build(),
+            Err(GameError::DiagonalNotAllowed(ShipClass::Destroyer))
+        );
+    }
+}
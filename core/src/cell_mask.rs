@@ -0,0 +1,107 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+
+use risc0_zkvm::sha::{Digest, Sha256};
+
+use crate::{GameState, Position};
+
+/// A set of cells on the board, as a bitboard with bit [Position::to_index] set for each cell in
+/// the set.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CellMask(pub u128);
+
+impl CellMask {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the set of the given cells. Panics if any cell is out of bounds.
+    pub fn from_positions(positions: impl IntoIterator<Item = Position>) -> Self {
+        let mut mask = Self::new();
+        for pos in positions {
+            mask.insert(pos);
+        }
+        mask
+    }
+
+    /// Adds the cell to the set. Panics if the cell is out of bounds.
+    pub fn insert(&mut self, pos: Position) {
+        let index = pos.to_index().expect("cell should be on the board");
+        self.0 |= 1 << index;
+    }
+
+    /// Returns true if the cell is in the set. Cells off the board are never in the set.
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.to_index()
+            .is_some_and(|index| self.0 & (1 << index) != 0)
+    }
+
+    /// Returns true if any cell is in both sets.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Digest of the little-endian bytes of the mask, committed by guests alongside the board so
+    /// that the verifier can check which cells the board was checked against.
+    pub fn digest(&self) -> Digest {
+        *risc0_zkvm::sha::Impl::hash_bytes(&self.0.to_le_bytes())
+    }
+}
+
+impl GameState {
+    /// Returns the set of cells occupied by at least one ship. Any cells off the board are left
+    /// out, and should be rejected with [GameState::check] first.
+    pub fn occupancy(&self) -> CellMask {
+        CellMask::from_positions(
+            self.ships
+                .iter()
+                .flat_map(|ship| ship.points())
+                .filter(Position::in_bounds),
+        )
+    }
+
+    /// Returns true if no ship occupies any of the forbidden cells.
+    pub fn avoids(&self, forbidden: &CellMask) -> bool {
+        !self.occupancy().intersects(forbidden)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::example_state;
+
+    #[test]
+    fn occupancy() {
+        let state = example_state();
+
+        let occupancy = state.occupancy();
+        assert_eq!(occupancy.count() as usize, state.occupied_count());
+        for pos in Position::all() {
+            assert_eq!(occupancy.contains(pos), state.ship_at(pos).is_some());
+        }
+
+        let center = CellMask::from_positions(Position::range((4, 4), (5, 5)));
+        assert!(state.avoids(&center));
+        let with_carrier = CellMask::from_positions([Position { x: 2, y: 7 }]);
+        assert!(!state.avoids(&with_carrier));
+        assert_ne!(center.digest(), with_carrier.digest());
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+
+use risc0_zkvm::sha::Digest;
+
+use crate::{Position, ShipClass};
+
+/// Errors that arise from an invalid game state or use of the game rules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GameError {
+    /// A ship of the given class does not fit within the bounds of the board.
+    OutOfBounds(ShipClass),
+    /// The ship of the given class has a length of zero.
+    ZeroLength(ShipClass),
+    /// The ship of the given class has hits recorded beyond its length.
+    InvalidHitMask(ShipClass),
+    /// The pepper has the given length in bytes, which is shorter than [crate::MIN_PEPPER_LEN].
+    PepperTooShort(usize),
+    /// More than one ship of the given class is on the board.
+    DuplicateClass(ShipClass),
+    /// No ship of the given class is on the board.
+    MissingClass(ShipClass),
+    /// The ship of the given class is placed diagonally, which the rules do not allow.
+    DiagonalNotAllowed(ShipClass),
+    /// Ships of the two given classes occupy at least one of the same cells.
+    Overlap(ShipClass, ShipClass),
+    /// The game did not end within the given number of turns.
+    TurnLimitExceeded(usize),
+    /// The game did not end before the sequence of shots ran out.
+    ShotsExhausted,
+    /// The shot at the given position is outside the bounds of the board.
+    ShotOutOfBounds(Position),
+    /// The shot at the given position targets a ship that is already sunk, under rules where sunk
+    /// ships are revealed.
+    ShotAtSunkShip(Position),
+    /// The shot at the given position appears more than once in a sequence of shots.
+    DuplicateShot(Position),
+    /// The round does not continue from the state committed by the previous round.
+    StateMismatch,
+    /// The round applied the given shot instead of the expected one.
+    ShotMismatch(Position),
+    /// The opening of the cell under the shot in the round does not match its result.
+    InvalidTransition,
+    /// The revealed shot does not match the shot committed to before the round.
+    ShotRevealMismatch,
+    /// A shot was revealed without first committing to it.
+    MissingShotCommitment,
+    /// The cell index is not the index of a cell on the board.
+    InvalidCellIndex(u8),
+    /// The game has already ended, so no more shots can be taken.
+    GameOver,
+    /// The board was validated against a fleet with a different digest than the agreed fleet.
+    FleetMismatch { expected: Digest, actual: Digest },
+    /// The round at the given index, counting from zero, differs from the result of replaying its
+    /// shot on the revealed board.
+    RoundMismatch(usize),
+    /// The proof was made for a different match context than the agreed one.
+    ContextMismatch,
+    /// Shots were played on a toroidal board, which only the placement checks support.
+    ToroidalPlay,
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::OutOfBounds(class) => write!(f, "{:?} is out of bounds", class),
+            Self::ZeroLength(class) => write!(f, "{:?} has a length of zero", class),
+            Self::InvalidHitMask(class) => {
+                write!(f, "{:?} has hits beyond its length", class)
+            }
+            Self::PepperTooShort(len) => write!(f, "pepper of {} bytes is too short", len),
+            Self::DuplicateClass(class) => write!(f, "more than one {:?} on the board", class),
+            Self::MissingClass(class) => write!(f, "no {:?} on the board", class),
+            Self::DiagonalNotAllowed(class) => write!(f, "{:?} is placed diagonally", class),
+            Self::Overlap(a, b) => write!(f, "{:?} overlaps with {:?}", a, b),
+            Self::TurnLimitExceeded(max_turns) => {
+                write!(f, "game did not end within {} turns", max_turns)
+            }
+            Self::ShotsExhausted => write!(f, "game did not end before the shots ran out"),
+            Self::ShotOutOfBounds(pos) => write!(f, "shot at {} is out of bounds", pos),
+            Self::ShotAtSunkShip(pos) => write!(f, "shot at {} targets a sunk ship", pos),
+            Self::DuplicateShot(pos) => write!(f, "shot at {} is repeated", pos),
+            Self::StateMismatch => write!(f, "round does not continue from the expected state"),
+            Self::ShotMismatch(pos) => {
+                write!(f, "round applied shot {} instead of the expected shot", pos)
+            }
+            Self::InvalidTransition => {
+                write!(
+                    f,
+                    "opening of the shot in the round does not match its result"
+                )
+            }
+            Self::ShotRevealMismatch => write!(f, "revealed shot does not match the commitment"),
+            Self::MissingShotCommitment => write!(f, "shot was revealed without a commitment"),
+            Self::InvalidCellIndex(index) => write!(f, "cell index {} is off the board", index),
+            Self::GameOver => write!(f, "the game has already ended"),
+            Self::FleetMismatch { expected, actual } => write!(
+                f,
+                "board was validated against fleet {} instead of the agreed fleet {}",
+                actual, expected
+            ),
+            Self::RoundMismatch(index) => {
+                write!(f, "round {} does not match the revealed board", index)
+            }
+            Self::ContextMismatch => write!(f, "proof was made for a different match"),
+            Self::ToroidalPlay => write!(f, "shots on a toroidal board are not supported"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// Errors that arise when generating a random game state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GenError {
+    /// The fleet could not be placed within the given number of attempts.
+    AttemptsExhausted(u32),
+}
+
+impl Display for GenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::AttemptsExhausted(attempts) => {
+                write!(f, "could not place the fleet in {} attempts", attempts)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GenError {}
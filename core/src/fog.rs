@@ -0,0 +1,579 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::ControlFlow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Fleet, GameState, HitType, Position, RoundCommit, Ship, ShipClass, BOARD_SIZE};
+
+/// What the shooting player knows about a single cell of their opponent's board.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, Hash)]
+pub enum Knowledge {
+    #[default]
+    Unknown,
+    Miss,
+    Hit,
+    /// The shot at this cell sunk a ship of the given class.
+    Sunk(ShipClass),
+}
+
+/// The opponent-visible view of a board, derived only from the history of shots and results.
+///
+/// This does not contain any information about ship locations beyond what has been revealed by
+/// the results of each round.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FogBoard {
+    /// Knowledge of each cell, indexed as `cells[y][x]`.
+    pub cells: [[Knowledge; BOARD_SIZE]; BOARD_SIZE],
+}
+
+impl FogBoard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reconstruct the fog board from a sequence of shots and their results.
+    pub fn from_shots<'a>(shots: impl IntoIterator<Item = &'a (Position, HitType)>) -> Self {
+        let mut board = Self::new();
+        for (shot, hit) in shots {
+            board.record(*shot, hit);
+        }
+        board
+    }
+
+    /// Record the result of a shot. Shots that are out of bounds or invalid are ignored.
+    pub fn record(&mut self, shot: Position, hit: &HitType) {
+        if !shot.in_bounds() {
+            return;
+        }
+        let cell = &mut self.cells[shot.y as usize][shot.x as usize];
+        *cell = match hit {
+            HitType::Miss => Knowledge::Miss,
+            HitType::Hit => Knowledge::Hit,
+            HitType::Sunk(class) => Knowledge::Sunk(*class),
+            HitType::Invalid | HitType::Deflected => return,
+        };
+    }
+
+    /// Record the cells of a sunk ship, as revealed by [RoundCommit::sunk_cells]. Cells that are
+    /// out of bounds are ignored.
+    pub fn record_sunk(&mut self, class: ShipClass, cells: &[Position]) {
+        for cell in cells.iter().filter(|cell| cell.in_bounds()) {
+            self.cells[cell.y as usize][cell.x as usize] = Knowledge::Sunk(class);
+        }
+    }
+
+    /// Record the result of a proven round, marking every cell of the ship it sunk, if any.
+    pub fn record_round(&mut self, round: &RoundCommit) {
+        self.record(round.shot.into(), &round.hit);
+        if let (HitType::Sunk(class), Some(cells)) = (&round.hit, &round.sunk_cells) {
+            self.record_sunk(*class, cells);
+        }
+    }
+
+    /// Get the knowledge at the given position, or `None` if the position is out of bounds.
+    pub fn get(&self, pos: Position) -> Option<Knowledge> {
+        pos.in_bounds()
+            .then(|| self.cells[pos.y as usize][pos.x as usize])
+    }
+
+    /// Number of cells known to be hit, which matches [crate::GameState::damage_taken] of the
+    /// opponent's board.
+    pub fn damage(&self) -> u32 {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|knowledge| matches!(knowledge, Knowledge::Hit | Knowledge::Sunk(_)))
+            .count() as u32
+    }
+
+    fn at(&self, pos: Position) -> Knowledge {
+        self.cells[pos.y as usize][pos.x as usize]
+    }
+
+    /// Checks whether there exists at least one placement of the given fleet that is consistent
+    /// with every hit, miss, and sunk result recorded on this board.
+    pub fn is_consistent(&self, fleet: &Fleet) -> bool {
+        self.search_boards(fleet, &mut |_| ControlFlow::Break(()))
+            .is_break()
+    }
+
+    /// Checks whether the given board could have produced every result recorded on this board:
+    /// each ship is allowed by the results as in [FogBoard::is_consistent], and each hit or sunk
+    /// cell is covered by a ship. The hit masks of the ships are not considered.
+    pub fn is_consistent_with(&self, state: &GameState) -> bool {
+        state.ships.iter().all(|ship| self.allows(ship))
+            && Position::all().all(|pos| match self.at(pos) {
+                Knowledge::Hit | Knowledge::Sunk(_) => state.ship_at(pos).is_some(),
+                Knowledge::Unknown | Knowledge::Miss => true,
+            })
+    }
+
+    /// Checks whether the ship could be placed on the opponent's board given the recorded results,
+    /// without considering the other ships in the fleet.
+    fn allows(&self, ship: &Ship) -> bool {
+        let mut sunk_here = false;
+        let mut all_hit = true;
+        for pos in ship.points() {
+            match self.at(pos) {
+                Knowledge::Miss => return false,
+                Knowledge::Unknown => all_hit = false,
+                Knowledge::Hit => {}
+                Knowledge::Sunk(class) if class == ship.class => sunk_here = true,
+                Knowledge::Sunk(_) => return false,
+            }
+        }
+        // A ship is reported sunk exactly when every cell it occupies has been hit.
+        sunk_here == all_hit
+    }
+
+    /// Backtracking search over all placements of the fleet consistent with this board. The
+    /// visitor is called once for each consistent placement, and the search stops early if it
+    /// returns [ControlFlow::Break].
+    pub(crate) fn search_boards(
+        &self,
+        fleet: &Fleet,
+        visit: &mut impl FnMut(&[Ship]) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.search_boards_from(fleet, Vec::new(), visit)
+    }
+
+    /// Like [FogBoard::search_boards], but only over boards containing the given ships, which must
+    /// not overlap and are not counted against the fleet.
+    fn search_boards_from(
+        &self,
+        fleet: &Fleet,
+        placed: Vec<Ship>,
+        visit: &mut impl FnMut(&[Ship]) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let mut search = BoardSearch {
+            fog: self,
+            candidates: ShipClass::list()
+                .iter()
+                .map(|class| {
+                    class
+                        .valid_placements()
+                        .filter(|ship| self.allows(ship))
+                        .collect()
+                })
+                .collect(),
+            remaining: fleet.classes.clone(),
+            placed: Vec::new(),
+            occupied: [[false; BOARD_SIZE]; BOARD_SIZE],
+        };
+        for ship in placed {
+            search.set_occupied(&ship, true);
+            search.placed.push(ship);
+        }
+        search.run(visit)
+    }
+}
+
+/// Returns the classes in the fleet that have not been reported sunk on the fog board, and so may
+/// still be afloat. Unlike [FogBoard::is_consistent], this does not check that the remaining ships
+/// can actually be placed.
+pub fn surviving_candidates(fog: &FogBoard, fleet: &Fleet) -> Vec<ShipClass> {
+    let mut classes = fleet.classes.clone();
+    for knowledge in fog.cells.iter().flatten() {
+        if let Knowledge::Sunk(class) = knowledge {
+            if let Some(i) = classes.iter().position(|c| c == class) {
+                classes.remove(i);
+            }
+        }
+    }
+    classes
+}
+
+/// Returns true when exactly one placement of the fleet is consistent with the fog board, so the
+/// location of every ship, found or not, is pinned down by the recorded results.
+pub fn is_fully_determined(fog: &FogBoard, fleet: &Fleet) -> bool {
+    let mut first: Option<Vec<Ship>> = None;
+    let mut solutions = 0;
+    let _ = fog.search_boards(fleet, &mut |ships| {
+        // Fleets with repeated classes visit the same board once for each ordering of the
+        // repeated ships, so only boards with a different set of ships count as new solutions.
+        match &first {
+            None => {
+                first = Some(ships.to_vec());
+                solutions = 1;
+            }
+            Some(first) if ships.iter().all(|ship| first.contains(ship)) => {}
+            Some(_) => {
+                solutions = 2;
+                return ControlFlow::Break(());
+            }
+        }
+        ControlFlow::Continue(())
+    });
+    solutions == 1
+}
+
+/// Returns every placement of a ship of the given class that appears on at least one placement of
+/// the fleet consistent with the fog board. Unlike filtering [ShipClass::valid_placements] by the
+/// results on the cells of the ship alone, this accounts for the other ships, which must cover the
+/// remaining hits. Returns nothing if the class is not in the fleet.
+pub fn possible_placements(class: ShipClass, fog: &FogBoard, fleet: &Fleet) -> Vec<Ship> {
+    let Some(index) = fleet.classes.iter().position(|c| *c == class) else {
+        return Vec::new();
+    };
+    let mut rest = fleet.clone();
+    rest.classes.remove(index);
+    class
+        .valid_placements()
+        .filter(|ship| fog.allows(ship))
+        .filter(|ship| {
+            fog.search_boards_from(&rest, vec![ship.clone()], &mut |_| ControlFlow::Break(()))
+                .is_break()
+        })
+        .collect()
+}
+
+struct BoardSearch<'a> {
+    fog: &'a FogBoard,
+    /// Placements allowed by the fog board, for each class in the order of [ShipClass::list].
+    candidates: Vec<Vec<Ship>>,
+    remaining: Vec<ShipClass>,
+    placed: Vec<Ship>,
+    occupied: [[bool; BOARD_SIZE]; BOARD_SIZE],
+}
+
+impl BoardSearch<'_> {
+    fn run(&mut self, visit: &mut impl FnMut(&[Ship]) -> ControlFlow<()>) -> ControlFlow<()> {
+        // Branch on the ships that could cover the first hit not yet covered by a placed ship. Each
+        // consistent board has exactly one ship covering that cell, so each is visited only once.
+        let uncovered_hit = Position::all().find(|pos| {
+            !matches!(self.fog.at(*pos), Knowledge::Unknown | Knowledge::Miss)
+                && !self.is_occupied(*pos)
+        });
+
+        let classes: Vec<ShipClass> = match uncovered_hit {
+            Some(_) => {
+                let mut classes = Vec::new();
+                for class in self.remaining.iter() {
+                    if !classes.contains(class) {
+                        classes.push(*class);
+                    }
+                }
+                classes
+            }
+            // All hits are covered; place the remaining ships in order, or accept the board.
+            None => match self.remaining.first() {
+                Some(class) => vec![*class],
+                None => return visit(&self.placed),
+            },
+        };
+
+        for class in classes {
+            let index = self.remaining.iter().position(|c| *c == class).unwrap();
+            self.remaining.remove(index);
+            for i in 0..self.candidates[class.index()].len() {
+                let ship = self.candidates[class.index()][i].clone();
+                if uncovered_hit.is_some_and(|hit| !ship.points().any(|p| p == hit)) {
+                    continue;
+                }
+                if ship.points().any(|p| self.is_occupied(p)) {
+                    continue;
+                }
+                self.set_occupied(&ship, true);
+                self.placed.push(ship);
+                let flow = self.run(visit);
+                let ship = self.placed.pop().unwrap();
+                self.set_occupied(&ship, false);
+                if flow.is_break() {
+                    return flow;
+                }
+            }
+            self.remaining.insert(index, class);
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn is_occupied(&self, pos: Position) -> bool {
+        self.occupied[pos.y as usize][pos.x as usize]
+    }
+
+    fn set_occupied(&mut self, ship: &Ship, occupied: bool) {
+        for pos in ship.points() {
+            self.occupied[pos.y as usize][pos.x as usize] = occupied;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::example_state, Direction};
+
+    #[test]
+    fn example_game_fog() {
+        // Board
+        //  | 0 1 2 3 4 5 6 7 8 9 |
+        // 0|                     |
+        // 1|       B B B B       |
+        // 2|                     |
+        // 3|     A               |
+        // 4|     A               |
+        // 5|     A         S S S |
+        // 6|     A               |
+        // 7|     A   C     D D   |
+        // 8|         C           |
+        // 9|         C           |
+        let mut state = example_state();
+
+        // Shots from the example game, up to and including sinking the destroyer.
+        let shots: Vec<(Position, HitType)> = [
+            (1, 1),
+            (2, 5),
+            (3, 5),
+            (2, 6),
+            (2, 7),
+            (2, 8),
+            (2, 4),
+            (2, 3),
+            (4, 9),
+            (4, 8),
+            (4, 7),
+            (7, 2),
+            (7, 7),
+            (6, 7),
+            (8, 7),
+        ]
+        .into_iter()
+        .map(|shot| (shot.into(), state.apply_shot(shot)))
+        .collect();
+        let fog = FogBoard::from_shots(&shots);
+
+        // Fog (o = miss, x = hit, letter = sunk)
+        //  | 0 1 2 3 4 5 6 7 8 9 |
+        // 0|                     |
+        // 1|   o                 |
+        // 2|               o     |
+        // 3|     A               |
+        // 4|     x               |
+        // 5|     x o             |
+        // 6|     x               |
+        // 7|     x   C   o x D   |
+        // 8|     o   x           |
+        // 9|         x           |
+        use Knowledge::*;
+        let expected = [
+            ((1, 1), Miss),
+            ((2, 3), Sunk(ShipClass::Carrier)),
+            ((2, 4), Hit),
+            ((2, 5), Hit),
+            ((3, 5), Miss),
+            ((2, 6), Hit),
+            ((2, 7), Hit),
+            ((2, 8), Miss),
+            ((4, 7), Sunk(ShipClass::Cruiser)),
+            ((4, 8), Hit),
+            ((4, 9), Hit),
+            ((7, 2), Miss),
+            ((6, 7), Miss),
+            ((7, 7), Hit),
+            ((8, 7), Sunk(ShipClass::Destroyer)),
+        ];
+        for y in 0..BOARD_SIZE as u32 {
+            for x in 0..BOARD_SIZE as u32 {
+                let pos = Position { x, y };
+                let knowledge = expected
+                    .iter()
+                    .find(|(p, _)| Position::from(*p) == pos)
+                    .map_or(Unknown, |(_, k)| *k);
+                assert_eq!(
+                    fog.get(pos),
+                    Some(knowledge),
+                    "unexpected knowledge at {pos}"
+                );
+            }
+        }
+        assert_eq!(fog.get(Position { x: 10, y: 0 }), None);
+        assert_eq!(fog.damage(), state.damage_taken());
+        assert!(fog.is_consistent(&Fleet::standard()));
+        assert!(fog.is_consistent_with(&state));
+
+        // Moving the submarine, which has not been found, is still consistent. Moving the
+        // destroyer, which has been sunk, is not.
+        let mut moved = state.clone();
+        moved.ships[3] = Ship::new(ShipClass::Submarine, (6, 0), Direction::Horizontal);
+        assert!(fog.is_consistent_with(&moved));
+        moved.ships[4] = Ship::new(ShipClass::Destroyer, (7, 8), Direction::Horizontal);
+        assert!(!fog.is_consistent_with(&moved));
+    }
+
+    #[test]
+    fn round_marks_sunk_ship() {
+        let mut state = example_state();
+        let mut fog = FogBoard::new();
+        for shot in [(7, 7), (8, 7)] {
+            fog.record_round(&state.play_round(shot.into()).unwrap());
+        }
+
+        // The round that sinks the destroyer reveals both of its cells.
+        for pos in [(7, 7), (8, 7)] {
+            assert_eq!(
+                fog.get(pos.into()),
+                Some(Knowledge::Sunk(ShipClass::Destroyer))
+            );
+        }
+        assert_eq!(fog.damage(), 2);
+    }
+
+    #[test]
+    fn repeated_shot_keeps_sunk_marker() {
+        // A repeated shot at a sunk ship reports it sunk again.
+        let mut fog = FogBoard::new();
+        fog.record((0, 0).into(), &HitType::Sunk(ShipClass::Destroyer));
+        fog.record((0, 0).into(), &HitType::Sunk(ShipClass::Destroyer));
+        assert_eq!(
+            fog.get((0, 0).into()),
+            Some(Knowledge::Sunk(ShipClass::Destroyer))
+        );
+
+        // Invalid or deflected shots leave the cell untouched.
+        fog.record((0, 0).into(), &HitType::Invalid);
+        fog.record((0, 0).into(), &HitType::Deflected);
+        assert_eq!(
+            fog.get((0, 0).into()),
+            Some(Knowledge::Sunk(ShipClass::Destroyer))
+        );
+    }
+
+    #[test]
+    fn surviving_classes() {
+        let fog = FogBoard::from_shots(&[
+            ((0, 0).into(), HitType::Hit),
+            ((1, 0).into(), HitType::Sunk(ShipClass::Destroyer)),
+            ((5, 5).into(), HitType::Miss),
+            ((3, 4).into(), HitType::Sunk(ShipClass::Submarine)),
+        ]);
+        assert_eq!(
+            surviving_candidates(&fog, &Fleet::standard()),
+            vec![
+                ShipClass::Carrier,
+                ShipClass::Battleship,
+                ShipClass::Cruiser
+            ]
+        );
+    }
+
+    #[test]
+    fn fully_determined() {
+        let mut state = example_state();
+
+        // Sink every ship but the destroyer, and miss every empty cell but (9, 7).
+        let mut fog = FogBoard::new();
+        for ship in state.ships.clone() {
+            if ship.class != ShipClass::Destroyer {
+                for pos in ship.points() {
+                    fog.record(pos, &state.apply_shot(pos));
+                }
+            }
+        }
+        let open = Position { x: 9, y: 7 };
+        for pos in Position::all() {
+            if state.ship_at(pos).is_none() && pos != open {
+                fog.record(pos, &HitType::Miss);
+            }
+        }
+        let fleet = Fleet::standard();
+
+        // The destroyer could sit on (7, 7) and (8, 7), or (8, 7) and (9, 7).
+        assert!(fog.is_consistent(&fleet));
+        assert!(!is_fully_determined(&fog, &fleet));
+
+        // A miss on (9, 7) leaves only one place for it.
+        fog.record(open, &HitType::Miss);
+        assert!(is_fully_determined(&fog, &fleet));
+        assert!(fog.is_consistent_with(&state));
+
+        // A board no placement fits is not determined either.
+        fog.record(Position { x: 7, y: 7 }, &HitType::Miss);
+        assert!(!fog.is_consistent(&fleet));
+        assert!(!is_fully_determined(&fog, &fleet));
+    }
+
+    #[test]
+    fn destroyer_placements() {
+        // A hit on (5, 5), with misses above and to the left.
+        let fog = FogBoard::from_shots(&[
+            ((5, 5).into(), HitType::Hit),
+            ((5, 4).into(), HitType::Miss),
+            ((4, 5).into(), HitType::Miss),
+        ]);
+
+        // If the destroyer is the only ship left, it must cover the hit, extending right or down.
+        let fleet = Fleet {
+            classes: vec![ShipClass::Destroyer],
+        };
+        assert_eq!(
+            possible_placements(ShipClass::Destroyer, &fog, &fleet),
+            vec![
+                Ship::new(ShipClass::Destroyer, (5, 5), Direction::Horizontal),
+                Ship::new(ShipClass::Destroyer, (5, 5), Direction::Vertical),
+            ]
+        );
+
+        // With the full fleet another ship may cover the hit, so the destroyer need not touch it.
+        let placements = possible_placements(ShipClass::Destroyer, &fog, &Fleet::standard());
+        assert!(placements.len() > 2);
+        assert!(placements.iter().all(|ship| fog.allows(ship)));
+
+        // No class outside the fleet can be placed.
+        assert!(possible_placements(ShipClass::Carrier, &fog, &fleet).is_empty());
+    }
+
+    #[test]
+    fn empty_fog_is_consistent() {
+        assert!(FogBoard::new().is_consistent(&Fleet::standard()));
+    }
+
+    #[test]
+    fn inconsistent_fog() {
+        // A hit in the corner, with misses on either side, cannot be covered by any ship.
+        let fog = FogBoard::from_shots(&[
+            ((0, 0).into(), HitType::Hit),
+            ((1, 0).into(), HitType::Miss),
+            ((0, 1).into(), HitType::Miss),
+        ]);
+        assert!(!fog.is_consistent(&Fleet::standard()));
+
+        // Two adjacent hits, fenced in by misses, would have sunk the destroyer.
+        let fog = FogBoard::from_shots(&[
+            ((0, 0).into(), HitType::Hit),
+            ((1, 0).into(), HitType::Hit),
+            ((2, 0).into(), HitType::Miss),
+            ((0, 1).into(), HitType::Miss),
+            ((1, 1).into(), HitType::Miss),
+        ]);
+        assert!(!fog.is_consistent(&Fleet::standard()));
+
+        // The same hits are consistent if the second one sunk the destroyer.
+        let fog = FogBoard::from_shots(&[
+            ((0, 0).into(), HitType::Hit),
+            ((1, 0).into(), HitType::Sunk(ShipClass::Destroyer)),
+            ((2, 0).into(), HitType::Miss),
+            ((0, 1).into(), HitType::Miss),
+            ((1, 1).into(), HitType::Miss),
+        ]);
+        assert!(fog.is_consistent(&Fleet::standard()));
+
+        // A ship cannot be sunk if it is not in the fleet.
+        let fleet = Fleet {
+            classes: vec![ShipClass::Carrier, ShipClass::Battleship],
+        };
+        assert!(!fog.is_consistent(&fleet));
+    }
+}
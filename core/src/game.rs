@@ -0,0 +1,222 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+
+use risc0_zkvm::sha::Digest;
+
+use crate::{Fleet, GameError, GameState, HitType, Position, RoundCommit, BOARD_SIZE};
+
+/// How hits on the segments of a ship count towards sinking it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SinkRule {
+    /// Segments may be hit in any order.
+    #[default]
+    AnyOrder,
+    /// Segments must be hit in order from bow to stern. A shot at a segment before the ones ahead
+    /// of it are hit is [HitType::Deflected].
+    InOrder,
+}
+
+/// Rules for running a game to completion.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GameConfig {
+    /// Maximum number of turns before the game is stopped, so that a game where the shooting
+    /// player stalls is detected rather than running forever.
+    pub max_turns: usize,
+    /// Whether ships may be placed diagonally, as allowed by some house rules.
+    pub allow_diagonal: bool,
+    /// Ship classes that every board must contain exactly once.
+    pub fleet: Fleet,
+    /// Whether the board wraps around at the edges, for experimental variants. Only affects the
+    /// placement checks in [GameState::check_with]. Shots are resolved without wrapping, so the
+    /// cells of a ship that wrap around could never be hit, and [replay] rejects toroidal boards.
+    pub toroidal: bool,
+    pub sink_rule: SinkRule,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        // Shooting every cell once is always enough to sink every ship.
+        Self {
+            max_turns: BOARD_SIZE * BOARD_SIZE,
+            allow_diagonal: false,
+            fleet: Fleet::standard(),
+            toroidal: false,
+            sink_rule: SinkRule::AnyOrder,
+        }
+    }
+}
+
+/// Applies shots to the state until every ship is sunk, returning the result of each shot.
+///
+/// Returns [GameError::TurnLimitExceeded] if the fleet has not been defeated within the configured
+/// turn limit, and [GameError::ShotsExhausted] if the shots run out first. Returns
+/// [GameError::ToroidalPlay] if the config has a toroidal board, on which shots cannot be resolved.
+pub fn replay(
+    state: &mut GameState,
+    shots: impl IntoIterator<Item = Position>,
+    config: &GameConfig,
+) -> Result<Vec<(Position, HitType)>, GameError> {
+    if config.toroidal {
+        return Err(GameError::ToroidalPlay);
+    }
+    let mut shots = shots.into_iter();
+    let mut results = Vec::new();
+    while !state.is_defeated() {
+        if results.len() >= config.max_turns {
+            return Err(GameError::TurnLimitExceeded(config.max_turns));
+        }
+        let Some(shot) = shots.next() else {
+            return Err(GameError::ShotsExhausted);
+        };
+        results.push((shot, state.apply_shot_with(shot, config.sink_rule)));
+    }
+    Ok(results)
+}
+
+/// Checks a board revealed at the end of a game against the commitments made during it. The board
+/// with its hits cleared must open the initial commitment, and replaying the shot of each round on
+/// it must reproduce that round exactly, ending in the revealed board. The match context of each
+/// round is not checked here; see [RoundCommit::check_context].
+///
+/// Returns [GameError::StateMismatch] if the board does not match the initial commitment or the
+/// final state, and [GameError::RoundMismatch] for the first round that differs from the replay.
+pub fn audit_game(
+    final_state: &GameState,
+    init_commit: &Digest,
+    rounds: &[RoundCommit],
+) -> Result<(), GameError> {
+    let mut state = final_state.clone();
+    for ship in state.ships.iter_mut() {
+        ship.hit_mask = 0;
+    }
+    if state.commit() != *init_commit {
+        return Err(GameError::StateMismatch);
+    }
+
+    for (index, round) in rounds.iter().enumerate() {
+        if state.is_defeated()
+            || state
+                .play_round(round.shot.into())
+                .map(|replayed| replayed.with_context(round.context))
+                .as_ref()
+                != Ok(round)
+        {
+            return Err(GameError::RoundMismatch(index));
+        }
+    }
+    if state != *final_state {
+        return Err(GameError::StateMismatch);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::example_state, ShipClass};
+
+    #[test]
+    fn all_misses_exceeds_turn_limit() {
+        let mut state = example_state();
+        let shots = std::iter::repeat(Position { x: 0, y: 0 });
+        assert_eq!(
+            replay(&mut state, shots, &GameConfig::default()),
+            Err(GameError::TurnLimitExceeded(BOARD_SIZE * BOARD_SIZE))
+        );
+    }
+
+    #[test]
+    fn sweep_finishes_within_turn_limit() {
+        let mut state = example_state();
+        let results = replay(&mut state, Position::all(), &GameConfig::default()).unwrap();
+        assert!(state.is_defeated());
+        assert_eq!(
+            results.last(),
+            Some(&(Position { x: 4, y: 9 }, HitType::Sunk(ShipClass::Cruiser)))
+        );
+
+        // Running out of shots before the game is over is also an error.
+        let mut state = example_state();
+        assert_eq!(
+            replay(
+                &mut state,
+                [Position { x: 2, y: 3 }],
+                &GameConfig::default()
+            ),
+            Err(GameError::ShotsExhausted)
+        );
+    }
+
+    #[test]
+    fn toroidal_replay_rejected() {
+        let mut state = example_state();
+        let config = GameConfig {
+            toroidal: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            replay(&mut state, Position::all(), &config),
+            Err(GameError::ToroidalPlay)
+        );
+        assert_eq!(state.damage_taken(), 0);
+    }
+
+    fn played_rounds(state: &mut GameState) -> Vec<RoundCommit> {
+        [(1, 1), (7, 7), (8, 7), (2, 5)]
+            .into_iter()
+            .map(|shot| state.play_round(shot.into()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn audit_clean_game() {
+        let mut state = example_state();
+        let init_commit = state.commit();
+        let rounds = played_rounds(&mut state);
+        assert_eq!(audit_game(&state, &init_commit, &rounds), Ok(()));
+
+        // A board other than the one committed to fails, even with the same ships.
+        let other = GameState {
+            pepper: vec![0; crate::PEPPER_LEN],
+            ..state.clone()
+        };
+        assert_eq!(
+            audit_game(&other, &init_commit, &rounds),
+            Err(GameError::StateMismatch)
+        );
+
+        // Revealing the board with hits that no round made also fails.
+        assert_eq!(
+            audit_game(&state, &init_commit, &rounds[..3]),
+            Err(GameError::StateMismatch)
+        );
+    }
+
+    #[test]
+    fn audit_tampered_round() {
+        let mut state = example_state();
+        let init_commit = state.commit();
+        let mut rounds = played_rounds(&mut state);
+
+        // Reporting a miss for the hit on the destroyer is caught, even though the commitments
+        // still chain.
+        rounds[1].hit = HitType::Miss;
+        assert_eq!(
+            audit_game(&state, &init_commit, &rounds),
+            Err(GameError::RoundMismatch(1))
+        );
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GameState, Position, BOARD_SIZE};
+
+/// Per-cell counts aggregated over many games, for analytics.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Heatmap {
+    /// Number of revealed boards with a ship on each cell, indexed as `occupied[y][x]`.
+    pub occupied: [[u32; BOARD_SIZE]; BOARD_SIZE],
+    /// Number of shots at each cell, indexed as `targeted[y][x]`.
+    pub targeted: [[u32; BOARD_SIZE]; BOARD_SIZE],
+}
+
+impl Heatmap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulate the cells occupied by ships on a revealed board.
+    pub fn record_board(&mut self, state: &GameState) {
+        for pos in state.ships.iter().flat_map(|ship| ship.points()) {
+            if pos.in_bounds() {
+                self.occupied[pos.y as usize][pos.x as usize] += 1;
+            }
+        }
+    }
+
+    /// Accumulate the cells targeted by a sequence of shots.
+    pub fn record_shots(&mut self, shots: impl IntoIterator<Item = Position>) {
+        for pos in shots {
+            if pos.in_bounds() {
+                self.targeted[pos.y as usize][pos.x as usize] += 1;
+            }
+        }
+    }
+
+    /// Write the heatmap as CSV, with a header and one row per cell in row-major order.
+    pub fn write_csv(&self, mut writer: impl Write) -> std::io::Result<()> {
+        writeln!(writer, "x,y,occupied,targeted")?;
+        for y in 0..BOARD_SIZE {
+            for x in 0..BOARD_SIZE {
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    x, y, self.occupied[y][x], self.targeted[y][x]
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::example_state, Direction, Ship, ShipClass};
+
+    #[test]
+    fn accumulate_boards() {
+        let state_a = example_state();
+        let state_b = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Carrier, (2, 3), Direction::Horizontal),
+                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
+                Ship::new(ShipClass::Cruiser, (0, 7), Direction::Vertical),
+                Ship::new(ShipClass::Submarine, (7, 5), Direction::Vertical),
+                Ship::new(ShipClass::Destroyer, (8, 0), Direction::Horizontal),
+            ],
+            ..GameState::new(rand::random())
+        };
+
+        let mut heatmap = Heatmap::new();
+        heatmap.record_board(&state_a);
+        heatmap.record_board(&state_b);
+        heatmap.record_shots([(2, 3).into(), (2, 3).into(), (9, 9).into()]);
+
+        // Both carriers start at the same cell, and both battleships are in the same place.
+        assert_eq!(heatmap.occupied[3][2], 2);
+        assert_eq!(heatmap.occupied[1][4], 2);
+        // Only the first board has its carrier running down from (2, 3).
+        assert_eq!(heatmap.occupied[4][2], 1);
+        // Only the second board has its carrier running across from (2, 3).
+        assert_eq!(heatmap.occupied[3][3], 1);
+        // Both submarines cover (7, 5), and the destroyer on the first board covers (7, 7).
+        assert_eq!(heatmap.occupied[5][7], 2);
+        assert_eq!(heatmap.occupied[7][7], 2);
+        assert_eq!(heatmap.occupied[0][0], 0);
+
+        let total: u32 = heatmap.occupied.iter().flatten().sum();
+        assert_eq!(total, 2 * 17);
+
+        assert_eq!(heatmap.targeted[3][2], 2);
+        assert_eq!(heatmap.targeted[9][9], 1);
+        assert_eq!(heatmap.targeted.iter().flatten().sum::<u32>(), 3);
+
+        let mut csv = Vec::new();
+        heatmap.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + BOARD_SIZE * BOARD_SIZE);
+        assert_eq!(lines[0], "x,y,occupied,targeted");
+        assert_eq!(lines[1 + 3 * BOARD_SIZE + 2], "2,3,2,2");
+    }
+}
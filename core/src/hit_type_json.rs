@@ -0,0 +1,115 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A stable, tagged JSON representation of [HitType] for clients not written in Rust.
+//!
+//! The derived encoding of [HitType] is kept as-is for bincode and the guests. Use this module
+//! with `#[serde(with = "battleship_core::hit_type_json")]` on a field to encode the result as
+//! `{"type":"sunk","class":"carrier"}`, `{"type":"miss"}`, and so on.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{HitType, ShipClass};
+
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Tagged {
+    Miss,
+    Hit,
+    Sunk { class: ClassName },
+    Invalid,
+    Deflected,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ClassName {
+    Carrier,
+    Battleship,
+    Cruiser,
+    Submarine,
+    Destroyer,
+}
+
+pub fn serialize<S: Serializer>(hit: &HitType, serializer: S) -> Result<S::Ok, S::Error> {
+    let tagged = match hit {
+        HitType::Miss => Tagged::Miss,
+        HitType::Hit => Tagged::Hit,
+        HitType::Sunk(class) => Tagged::Sunk {
+            class: match class {
+                ShipClass::Carrier => ClassName::Carrier,
+                ShipClass::Battleship => ClassName::Battleship,
+                ShipClass::Cruiser => ClassName::Cruiser,
+                ShipClass::Submarine => ClassName::Submarine,
+                ShipClass::Destroyer => ClassName::Destroyer,
+            },
+        },
+        HitType::Invalid => Tagged::Invalid,
+        HitType::Deflected => Tagged::Deflected,
+    };
+    tagged.serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HitType, D::Error> {
+    Ok(match Tagged::deserialize(deserializer)? {
+        Tagged::Miss => HitType::Miss,
+        Tagged::Hit => HitType::Hit,
+        Tagged::Sunk { class } => HitType::Sunk(match class {
+            ClassName::Carrier => ShipClass::Carrier,
+            ClassName::Battleship => ShipClass::Battleship,
+            ClassName::Cruiser => ShipClass::Cruiser,
+            ClassName::Submarine => ShipClass::Submarine,
+            ClassName::Destroyer => ShipClass::Destroyer,
+        }),
+        Tagged::Invalid => HitType::Invalid,
+        Tagged::Deflected => HitType::Deflected,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Outcome {
+        #[serde(with = "super")]
+        hit: HitType,
+    }
+
+    #[test]
+    fn tagged_round_trip() {
+        let cases = [
+            (HitType::Miss, r#"{"hit":{"type":"miss"}}"#),
+            (HitType::Hit, r#"{"hit":{"type":"hit"}}"#),
+            (
+                HitType::Sunk(ShipClass::Carrier),
+                r#"{"hit":{"type":"sunk","class":"carrier"}}"#,
+            ),
+            (
+                HitType::Sunk(ShipClass::Destroyer),
+                r#"{"hit":{"type":"sunk","class":"destroyer"}}"#,
+            ),
+            (HitType::Invalid, r#"{"hit":{"type":"invalid"}}"#),
+            (HitType::Deflected, r#"{"hit":{"type":"deflected"}}"#),
+        ];
+        for (hit, json) in cases {
+            let outcome = Outcome { hit };
+            assert_eq!(serde_json::to_string(&outcome).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Outcome>(json).unwrap(), outcome);
+        }
+
+        assert!(serde_json::from_str::<Outcome>(r#"{"hit":{"type":"sunk"}}"#).is_err());
+        assert!(serde_json::from_str::<Outcome>(r#"{"hit":"Miss"}"#).is_err());
+    }
+}
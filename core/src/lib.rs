@@ -14,6 +14,8 @@
 
 use std::fmt::Display;
 
+use anyhow::Context;
+use bincode::Options;
 #[cfg(feature = "rand")]
 use rand::{
     distr::{Distribution, StandardUniform},
@@ -22,12 +24,59 @@ use rand::{
 };
 use serde::{Deserialize, Serialize};
 
-use risc0_zkvm::sha::{Digest, Sha256};
+use risc0_zkvm::{
+    sha::{Digest, Sha256},
+    Receipt,
+};
+
+mod ai;
+mod builder;
+mod cell_mask;
+mod error;
+mod fog;
+mod game;
+mod heatmap;
+pub mod hit_type_json;
+mod merkle;
+mod render;
+#[cfg(feature = "schema")]
+mod schema;
+mod sim;
+#[cfg(test)]
+mod test_utils;
+#[cfg(test)]
+mod test_vectors;
+
+pub use ai::{opening_shots, placement_density, AiPlayer, Density};
+pub use builder::GameStateBuilder;
+pub use cell_mask::CellMask;
+pub use error::{GameError, GenError};
+pub use fog::{
+    is_fully_determined, possible_placements, surviving_candidates, FogBoard, Knowledge,
+};
+pub use game::{audit_game, replay, GameConfig, SinkRule};
+pub use heatmap::Heatmap;
+pub use merkle::{CellState, ShotOpening};
+#[cfg(feature = "schema")]
+pub use schema::emit_schema;
+pub use sim::{LocalMatch, Player};
 
 pub const NUM_SHIPS: usize = 5;
 pub const BOARD_SIZE: usize = 10;
+/// Length of the pepper in bytes for new game states, giving 256 bits of hiding entropy.
+pub const PEPPER_LEN: usize = 32;
+/// Minimum length of the pepper in bytes accepted by [GameState::check].
+pub const MIN_PEPPER_LEN: usize = 16;
+/// Number of attempts made to place the fleet when sampling a random [GameState].
+pub const MAX_SAMPLE_ATTEMPTS: u32 = 100;
+/// Number of ships from which [GameState::check_with] detects overlapping ships by accumulating a
+/// bitboard of the occupied cells, rather than comparing every pair of ships.
+const BITBOARD_MIN_SHIPS: usize = 8;
+/// Length in bytes of the encoding produced by [RoundSummary::to_bytes].
+pub const ROUND_SUMMARY_LEN: usize = 68;
 
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ShipClass {
     Carrier,
     Battleship,
@@ -60,21 +109,123 @@ impl ShipClass {
             Self::Destroyer,
         ]
     }
+
+    /// Character used for this class in board diagrams. The carrier uses 'A', since 'C' is used
+    /// by the cruiser.
+    pub fn to_char(&self) -> char {
+        match self {
+            ShipClass::Carrier => 'A',
+            ShipClass::Battleship => 'B',
+            ShipClass::Cruiser => 'C',
+            ShipClass::Submarine => 'S',
+            ShipClass::Destroyer => 'D',
+        }
+    }
+
+    /// Inverse of [ShipClass::to_char].
+    pub fn from_char(c: char) -> Option<Self> {
+        Self::list()
+            .iter()
+            .copied()
+            .find(|class| class.to_char() == c)
+    }
+
+    /// Position of this class in [ShipClass::list].
+    pub fn index(self) -> usize {
+        ShipClass::list().iter().position(|c| *c == self).unwrap()
+    }
+
+    /// Cells a ship of this class would occupy if placed at the given position and direction.
+    ///
+    /// Unlike [Ship::points], the placement is not required to be valid, and any cells that fall
+    /// outside the board are included as-is. This is useful for previewing a placement.
+    pub fn footprint(self, pos: impl Into<Position>, dir: Direction) -> Vec<Position> {
+        let pos = pos.into();
+        (0..self.span())
+            .map(|offset| pos.step(dir, offset))
+            .collect()
+    }
+
+    /// Enumerates every in-bounds placement of a ship of this class on the board.
+    pub fn valid_placements(self) -> impl Iterator<Item = Ship> {
+        [Direction::Horizontal, Direction::Vertical]
+            .into_iter()
+            .flat_map(move |dir| Position::all().map(move |pos| Ship::new(self, pos, dir)))
+            .filter(Ship::in_bounds)
+    }
+}
+
+/// The ship classes that make up a player's fleet.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Fleet {
+    pub classes: Vec<ShipClass>,
+}
+
+impl Fleet {
+    /// The standard fleet, with one ship of each class.
+    pub fn standard() -> Self {
+        Self {
+            classes: ShipClass::list().to_vec(),
+        }
+    }
+
+    /// Digest identifying the fleet, so that both players can commit to the same ruleset before
+    /// playing. The order of the classes does not affect the digest.
+    pub fn digest(&self) -> Digest {
+        let bytes = canonical_bincode()
+            .serialize(&self.sorted_classes())
+            .expect("fleet serialization should always succeed");
+        *risc0_zkvm::sha::Impl::hash_bytes(&bytes)
+    }
+
+    /// Checks whether both fleets are made up of the same ship classes, in any order.
+    pub fn eq_rules(&self, other: &Self) -> bool {
+        self.sorted_classes() == other.sorted_classes()
+    }
+
+    fn sorted_classes(&self) -> Vec<ShipClass> {
+        let mut classes = self.classes.clone();
+        classes.sort_by_key(|class| class.index());
+        classes
+    }
+}
+
+impl Default for Fleet {
+    fn default() -> Self {
+        Self::standard()
+    }
 }
 
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Position {
     pub x: u32,
     pub y: u32,
 }
 
+/// A cell on the board encoded as its row-major index from [Position::to_index], for compact
+/// journals. The journal serializes it as a single word, half the size of the two words of a
+/// [Position].
+///
+/// Only indices of cells on the board can be constructed or deserialized.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(try_from = "u8", into = "u8")]
+pub struct CellIndex(u8);
+
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Direction {
     Horizontal,
     Vertical,
+    /// Diagonal, running down and to the right. Only allowed if [GameConfig::allow_diagonal] is set.
+    DiagonalDown,
+    /// Diagonal, running up and to the right. Only allowed if [GameConfig::allow_diagonal] is set.
+    DiagonalUp,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Ship {
     pub class: ShipClass,
     pub pos: Position,
@@ -83,23 +234,38 @@ pub struct Ship {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct GameState {
     pub ships: Vec<Ship>,
-    /// Entropy added to the game state such that the commitment is hiding.
-    pub pepper: [u8; 16],
+    /// Entropy added to the game state such that the commitment is hiding. Must be at least
+    /// [MIN_PEPPER_LEN] bytes, and is [PEPPER_LEN] bytes by default.
+    pub pepper: Vec<u8>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct RoundInput {
     pub state: GameState,
     pub shot: Position,
+    /// Whether shots at ships that are already sunk are rejected, as under rules where sunk ships
+    /// are revealed.
+    pub strict: bool,
+    /// The [MatchContext::digest] of the match, if any, committed with the round so that its
+    /// proof cannot be reused in another match.
+    pub context: Option<Digest>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum HitType {
     Miss,
     Hit,
     Sunk(ShipClass),
+    /// The shot targeted a ship that was already sunk. Only returned by
+    /// [GameState::apply_shot_strict].
+    Invalid,
+    /// The shot struck a segment of a ship out of order, and did not count as a hit. Only returned
+    /// under [SinkRule::InOrder].
+    Deflected,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
@@ -109,384 +275,3231 @@ pub struct RoundOutput {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RoundCommit {
+    #[cfg_attr(feature = "schema", schemars(with = "[u32; 8]"))]
     pub old_state: Digest,
+    #[cfg_attr(feature = "schema", schemars(with = "[u32; 8]"))]
     pub new_state: Digest,
-    pub shot: Position,
+    /// The shot, encoded as a single byte to keep the journal small.
+    pub shot: CellIndex,
     pub hit: HitType,
+    /// Cells occupied by the ship sunk by this shot, revealed only when the result is
+    /// [HitType::Sunk].
+    pub sunk_cells: Option<Vec<Position>>,
+    /// Whether every ship has been sunk after this shot, ending the game.
+    pub game_over: bool,
+    /// Opening of the cell under the shot in the state commitments, binding the change to the
+    /// board to the result.
+    pub opening: ShotOpening,
+    /// The [MatchContext::digest] of the match the round was played in, if any.
+    #[cfg_attr(feature = "schema", schemars(with = "Option<[u32; 8]>"))]
+    pub context: Option<Digest>,
 }
 
-impl Ship {
-    pub fn points(&self) -> impl Iterator<Item = Position> + '_ {
-        (0..self.class.span()).map(|offset| self.pos.step(self.dir, offset))
-    }
-
-    pub fn intersects(&self, other: &Self) -> bool {
-        self.points().any(|p| other.points().any(|q| p == q))
-    }
-
-    pub fn in_bounds(&self) -> bool {
-        self.pos.in_bounds() && self.pos.step(self.dir, self.class.span() - 1).in_bounds()
+impl RoundCommit {
+    /// Binds the round to the given match context.
+    pub fn with_context(self, context: Option<Digest>) -> Self {
+        Self { context, ..self }
     }
-}
 
-impl GameState {
-    pub fn new(pepper: [u8; 16]) -> Self {
-        Self {
-            ships: Vec::new(),
-            pepper,
+    /// Checks that the round was played in the agreed match context, so that a proof from another
+    /// match cannot be replayed in this one.
+    pub fn check_context(&self, expected: Option<&Digest>) -> Result<(), GameError> {
+        match self.context.as_ref() == expected {
+            true => Ok(()),
+            false => Err(GameError::ContextMismatch),
         }
     }
 
-    /// Checks whether the game state contains a valid configuration of ships.
-    #[must_use]
-    pub fn check(&self) -> bool {
-        // Ensure every ship is in bounds.
-        for ship in self.ships.iter() {
-            if !ship.in_bounds() {
-                return false;
-            }
+    /// Checks that this round continues from the given state commitment, applied the expected
+    /// shot, and opens the cell under the shot consistently with its result. Returns the
+    /// commitment to the new state, which the next round must continue from.
+    pub fn verify_transition(
+        &self,
+        prev_commit: &Digest,
+        expected_shot: Position,
+    ) -> Result<Digest, GameError> {
+        if self.old_state != *prev_commit {
+            return Err(GameError::StateMismatch);
         }
-
-        // Ensure every ship class appears exactly once.
-        let mut classes = ShipClass::list().to_vec();
-        for ship in self.ships.iter() {
-            let Some(class_index) = classes.iter().position(|class| ship.class == *class) else {
-                return false;
-            };
-            classes.swap_remove(class_index);
+        if self.shot != expected_shot {
+            return Err(GameError::ShotMismatch(self.shot.into()));
         }
-        if !classes.is_empty() {
-            return false;
+        if !self
+            .opening
+            .check(self.shot, &self.hit, &self.old_state, &self.new_state)
+        {
+            return Err(GameError::InvalidTransition);
         }
+        Ok(self.new_state)
+    }
 
-        // Ensure no two ships are intersecting.
-        for (i, ship_i) in self.ships.iter().enumerate() {
-            for ship_j in self.ships.iter().skip(i + 1) {
-                if ship_i.intersects(ship_j) {
-                    return false;
-                }
-            }
+    /// Returns the public result of the round, without the sunk cells or the opening of the cell
+    /// under the shot.
+    pub fn summary(&self) -> RoundSummary {
+        RoundSummary {
+            old_state: self.old_state,
+            new_state: self.new_state,
+            shot: self.shot,
+            hit: self.hit.clone(),
+            game_over: self.game_over,
         }
+    }
 
-        true
+    /// Encodes the public result of the round in the fixed layout of [RoundSummary::to_bytes],
+    /// for verifiers such as settlement contracts that cannot decode bincode.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.summary().to_bytes()
     }
 
-    #[must_use]
-    pub fn add(&mut self, new_ship: Ship) -> bool {
-        if !new_ship.in_bounds() {
-            return false;
+    /// Hides the shot and its result behind a digest with the given nonce, which should be
+    /// chosen at random for each round.
+    pub fn blind(&self, nonce: [u8; 16]) -> BlindedRoundCommit {
+        BlindedRoundCommit {
+            old_state: self.old_state,
+            new_state: self.new_state,
+            outcome: self.reveal(nonce).digest(),
+            context: self.context,
         }
+    }
 
-        // Ensure that there is not already a ship with that class in the state.
-        for ship in self.ships.iter() {
-            if ship.class == new_ship.class {
-                return false;
-            }
-            if ship.intersects(&new_ship) {
-                return false;
-            }
+    /// Returns the opening for the commit produced by [RoundCommit::blind] with the same nonce.
+    pub fn reveal(&self, nonce: [u8; 16]) -> RoundReveal {
+        RoundReveal {
+            shot: self.shot,
+            hit: self.hit.clone(),
+            sunk_cells: self.sunk_cells.clone(),
+            game_over: self.game_over,
+            opening: self.opening.clone(),
+            nonce,
         }
-
-        self.ships.push(new_ship);
-        true
     }
+}
 
-    pub fn apply_shot(&mut self, shot: impl Into<Position>) -> HitType {
-        let shot = shot.into();
-        for ship in self.ships.iter_mut() {
-            let hit = ship.apply_shot(shot);
-            match hit {
-                HitType::Hit | HitType::Sunk(_) => return hit,
-                HitType::Miss => continue,
-            }
-        }
-        HitType::Miss
+/// A [RoundCommit] with the shot and its result replaced by a digest, which binds them without
+/// revealing them to observers of the journal.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BlindedRoundCommit {
+    pub old_state: Digest,
+    pub new_state: Digest,
+    /// Digest of the [RoundReveal] for this round.
+    pub outcome: Digest,
+    /// The match context of the round, which is public.
+    pub context: Option<Digest>,
+}
+
+impl BlindedRoundCommit {
+    /// Checks the reveal against this commit, and returns the full [RoundCommit] if it matches.
+    pub fn open(&self, reveal: &RoundReveal) -> Option<RoundCommit> {
+        (reveal.digest() == self.outcome).then(|| RoundCommit {
+            old_state: self.old_state,
+            new_state: self.new_state,
+            shot: reveal.shot,
+            hit: reveal.hit.clone(),
+            sunk_cells: reveal.sunk_cells.clone(),
+            game_over: reveal.game_over,
+            opening: reveal.opening.clone(),
+            context: self.context,
+        })
     }
+}
 
-    pub fn commit(&self) -> Digest {
-        let serialized_state =
-            bincode::serialize(&self).expect("state serialization should always succeed");
-        *risc0_zkvm::sha::Impl::hash_bytes(&serialized_state)
+/// The shot and result of a round, along with the nonce used to blind them.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RoundReveal {
+    pub shot: CellIndex,
+    pub hit: HitType,
+    pub sunk_cells: Option<Vec<Position>>,
+    pub game_over: bool,
+    pub opening: ShotOpening,
+    pub nonce: [u8; 16],
+}
+
+impl RoundReveal {
+    pub fn digest(&self) -> Digest {
+        let bytes = canonical_bincode()
+            .serialize(&self)
+            .expect("reveal serialization should always succeed");
+        *risc0_zkvm::sha::Impl::hash_bytes(&bytes)
     }
 }
 
-#[cfg(feature = "rand")]
-impl Distribution<GameState> for StandardUniform {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GameState {
-        // Create a shuffled list of all positions on the board.
-        let mut positions: Vec<Position> = (0..BOARD_SIZE)
-            .zip(0..BOARD_SIZE)
-            .map(|(x, y)| Position {
-                x: x as u32,
-                y: y as u32,
-            })
-            .collect();
-        positions.shuffle(rng);
+/// The public result of a round: the state commitments it chains between, the shot, and its
+/// result. Produced by [RoundCommit::summary].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RoundSummary {
+    pub old_state: Digest,
+    pub new_state: Digest,
+    pub shot: CellIndex,
+    pub hit: HitType,
+    pub game_over: bool,
+}
 
-        // Place the ships from largest to smallest, and using the shuffled positions.
-        let mut state = GameState::new(rng.random());
-        'outer: for ship_class in ShipClass::list() {
-            for pos in positions.iter() {
-                let dir = rng.random();
-                if state.add(Ship::new(*ship_class, *pos, dir)) {
-                    continue 'outer;
-                }
-                if state.add(Ship::new(*ship_class, *pos, dir.flip())) {
-                    continue 'outer;
-                }
-            }
-            unreachable!("did not find a position to place {:?}", ship_class);
-        }
+impl RoundSummary {
+    /// Encodes the summary in a fixed layout of [ROUND_SUMMARY_LEN] bytes:
+    ///
+    /// | Bytes    | Field                                                                  |
+    /// |----------|------------------------------------------------------------------------|
+    /// | `0..32`  | `old_state`                                                            |
+    /// | `32..64` | `new_state`                                                            |
+    /// | `64`     | `shot`, as the index of the [CellIndex]                                |
+    /// | `65`     | tag of `hit`: 0 miss, 1 hit, 2 sunk, 3 invalid, 4 deflected            |
+    /// | `66`     | index in [ShipClass::list] of the class sunk, or 0 if `hit` is not sunk |
+    /// | `67`     | `game_over`, as 0 or 1                                                 |
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (tag, class) = match self.hit {
+            HitType::Miss => (0, 0),
+            HitType::Hit => (1, 0),
+            HitType::Sunk(class) => (2, class.index() as u8),
+            HitType::Invalid => (3, 0),
+            HitType::Deflected => (4, 0),
+        };
+        let mut bytes = Vec::with_capacity(ROUND_SUMMARY_LEN);
+        bytes.extend_from_slice(self.old_state.as_bytes());
+        bytes.extend_from_slice(self.new_state.as_bytes());
+        bytes.extend_from_slice(&[self.shot.index(), tag, class, self.game_over as u8]);
+        bytes
+    }
 
-        // The resulting state should always be valid.
-        if !state.check() {
-            panic!("state is invalid: {:?}", state);
-        }
-        state
+    /// Decodes a summary produced by [RoundSummary::to_bytes], returning `None` if the bytes are
+    /// not of the expected length or any field is out of range.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let [shot, tag, class, game_over] = *bytes.get(64..)? else {
+            return None;
+        };
+        let hit = match (tag, class) {
+            (0, 0) => HitType::Miss,
+            (1, 0) => HitType::Hit,
+            (2, class) => HitType::Sunk(*ShipClass::list().get(class as usize)?),
+            (3, 0) => HitType::Invalid,
+            (4, 0) => HitType::Deflected,
+            _ => return None,
+        };
+        Some(Self {
+            old_state: Digest::try_from(&bytes[..32]).ok()?,
+            new_state: Digest::try_from(&bytes[32..64]).ok()?,
+            shot: CellIndex::try_from(shot).ok()?,
+            hit,
+            game_over: match game_over {
+                0 => false,
+                1 => true,
+                _ => return None,
+            },
+        })
     }
 }
 
-impl Position {
-    pub fn step(self, dir: Direction, dist: u32) -> Self {
-        match dir {
-            Direction::Vertical => Self {
-                x: self.x,
-                y: self.y + dist,
-            },
-            Direction::Horizontal => Self {
-                x: self.x + dist,
-                y: self.y,
-            },
+/// A shot and the nonce used to commit to it, sent by the shooter after the defender has
+/// acknowledged the commitment. Committing to the shot first prevents the defender from choosing
+/// how to respond based on the shot being made.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ShotReveal {
+    pub shot: Position,
+    pub nonce: [u8; 16],
+}
+
+impl ShotReveal {
+    /// Digest the shooter sends as the commitment to the shot, before revealing it.
+    pub fn digest(&self) -> Digest {
+        let bytes = canonical_bincode()
+            .serialize(&self)
+            .expect("reveal serialization should always succeed");
+        *risc0_zkvm::sha::Impl::hash_bytes(&bytes)
+    }
+
+    /// Checks the reveal against the commitment, returning the revealed shot if it matches.
+    pub fn open(&self, commitment: &Digest) -> Result<Position, GameError> {
+        match self.digest() == *commitment {
+            true => Ok(self.shot),
+            false => Err(GameError::ShotRevealMismatch),
         }
     }
+}
 
-    /// Check that the [Position] is within the bounds of the board.
-    #[must_use]
-    pub fn in_bounds(&self) -> bool {
-        self.x < BOARD_SIZE as u32 && self.y < BOARD_SIZE as u32
+impl RoundInput {
+    /// Describes exactly what the round guest will read, with the digest of the state before the
+    /// shot and the result expected from applying it, for diagnosing mismatched state commitments.
+    pub fn witness_summary(&self) -> String {
+        let expected_hit = self.state.clone().apply_shot(self.shot);
+        format!(
+            "round input: state {}, shot {}, expected result {:?}",
+            self.state.commit(),
+            self.shot,
+            expected_hit
+        )
     }
 }
 
-impl From<(u32, u32)> for Position {
-    fn from(value: (u32, u32)) -> Self {
+/// Input to the blinded round guest, which applies the round as the round guest does, but commits a
+/// [BlindedRoundCommit] in place of the [RoundCommit], so the shot and its result are hidden until
+/// revealed with a [RoundReveal]. The blinded round has its own guest, so that the journal of each
+/// image ID has a single type.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BlindedRoundInput {
+    pub round: RoundInput,
+    pub nonce: [u8; 16],
+}
+
+/// Input to the opening guest, which checks the initial state and then applies the first shots.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OpeningInput {
+    pub state: GameState,
+    pub shots: Vec<Position>,
+    /// The match context each of the opening rounds is bound to, as in [RoundInput::context].
+    pub context: Option<Digest>,
+}
+
+/// Journal of the opening guest, with a commitment to the validated initial state and the commit
+/// for each of the rounds applied to it.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OpeningCommit {
+    pub state: Digest,
+    pub rounds: Vec<RoundCommit>,
+}
+
+/// Input to the spectator guest, which applies a shot and reports only whether it was a hit.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SpectatorInput {
+    pub state: GameState,
+    pub shot: Position,
+    /// Total number of hits in the game before this shot.
+    pub hits: u32,
+}
+
+/// A reduced-disclosure [RoundCommit] for spectators, which does not reveal which class of ship
+/// was hit or sunk.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SpectatorCommit {
+    pub old_state: Digest,
+    pub new_state: Digest,
+    pub shot: Position,
+    /// Whether the shot hit a ship, including shots that sunk a ship.
+    pub is_hit: bool,
+    /// Total number of hits in the game, including this shot. Spectators should check this
+    /// continues from the count in the previous round.
+    pub hits: u32,
+}
+
+impl SpectatorCommit {
+    /// Reduces the commit for a round to what may be disclosed to spectators.
+    pub fn from_round(round_commit: &RoundCommit, prior_hits: u32) -> Self {
+        let is_hit = matches!(round_commit.hit, HitType::Hit | HitType::Sunk(_));
         Self {
-            x: value.0,
-            y: value.1,
+            old_state: round_commit.old_state,
+            new_state: round_commit.new_state,
+            shot: round_commit.shot.into(),
+            is_hit,
+            hits: prior_hits + is_hit as u32,
         }
     }
 }
 
-impl Display for Position {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
+/// Input to the hidden fleet guest, which applies a shot and reports only whether a ship was hit
+/// or sunk, and how many ships remain.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HiddenFleetInput {
+    pub state: GameState,
+    pub shot: Position,
 }
 
-impl Direction {
-    pub fn flip(self) -> Self {
-        match self {
-            Self::Horizontal => Self::Vertical,
-            Self::Vertical => Self::Horizontal,
-        }
-    }
+/// Result of a shot, as disclosed under rules that hide which class of ship was sunk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum HiddenOutcome {
+    /// The shot did not damage a ship, including shots that were invalid or deflected.
+    Miss,
+    Hit,
+    SunkSomething,
 }
 
-#[cfg(feature = "rand")]
-impl Distribution<Direction> for StandardUniform {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Direction {
-        match rng.random::<bool>() {
-            true => Direction::Horizontal,
-            false => Direction::Vertical,
+/// A reduced-disclosure [RoundCommit] for rules where a sinking is announced without its class.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HiddenFleetCommit {
+    pub old_state: Digest,
+    pub new_state: Digest,
+    pub shot: Position,
+    pub outcome: HiddenOutcome,
+    /// Number of ships on the board that are not yet sunk, after this shot.
+    pub ships_remaining: u8,
+}
+
+impl HiddenFleetCommit {
+    /// Reduces the commit for a round to what may be disclosed when the fleet is hidden.
+    pub fn from_round(round_commit: &RoundCommit, ships_remaining: u8) -> Self {
+        let outcome = match round_commit.hit {
+            HitType::Hit => HiddenOutcome::Hit,
+            HitType::Sunk(_) => HiddenOutcome::SunkSomething,
+            HitType::Miss | HitType::Invalid | HitType::Deflected => HiddenOutcome::Miss,
+        };
+        Self {
+            old_state: round_commit.old_state,
+            new_state: round_commit.new_state,
+            shot: round_commit.shot.into(),
+            outcome,
+            ships_remaining,
         }
     }
 }
 
-impl Ship {
-    pub fn new(class: ShipClass, pos: impl Into<Position>, dir: Direction) -> Self {
-        Ship {
-            class,
-            pos: pos.into(),
-            dir,
-            hit_mask: 0,
+/// Input to the adjacency hint guest, which applies a shot and, on a miss, also reports whether the
+/// shot landed next to a ship, for training variants.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AdjacencyHintInput {
+    pub state: GameState,
+    pub shot: Position,
+}
+
+/// A [RoundCommit] reduced to the shot and its result, with a hint of whether a ship is
+/// orthogonally adjacent to a missed shot.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AdjacencyHintCommit {
+    pub old_state: Digest,
+    pub new_state: Digest,
+    pub shot: Position,
+    pub hit: HitType,
+    /// Whether a ship occupies a cell next to the shot. Only disclosed on a [HitType::Miss], and
+    /// always false otherwise, since a hit already reveals a ship at the shot.
+    pub adjacent_to_ship: bool,
+}
+
+impl AdjacencyHintCommit {
+    /// Builds the commit for the round, taking the hint from the board the shot was applied to.
+    pub fn from_round(round_commit: &RoundCommit, state: &GameState) -> Self {
+        Self {
+            old_state: round_commit.old_state,
+            new_state: round_commit.new_state,
+            shot: round_commit.shot.into(),
+            hit: round_commit.hit.clone(),
+            adjacent_to_ship: round_commit.hit == HitType::Miss
+                && state.adjacent_to_ship(round_commit.shot.into()),
         }
     }
+}
 
-    pub fn with_hit_mask(self, hit_mask: u8) -> Self {
-        Self { hit_mask, ..self }
-    }
+/// Input to the damage report guest, which applies a batch of shots and reports only the total
+/// damage they dealt.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DamageReportInput {
+    pub state: GameState,
+    pub shots: Vec<Position>,
+}
 
-    pub fn apply_shot(&mut self, shot: Position) -> HitType {
-        let hit_index = self.points().position(|pos| pos == shot);
-        match hit_index {
-            Some(hit_index) => {
-                self.hit_mask |= 1 << hit_index;
-                match self.hit_mask == self.class.sunk_mask() {
-                    true => HitType::Sunk(self.class),
-                    false => HitType::Hit,
-                }
-            }
-            None => HitType::Miss,
+/// Journal of the damage report guest. Binds the batch of shots to the number of hits and sinks
+/// they scored in total, without disclosing which of the shots hit.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DamageReportCommit {
+    pub old_state: Digest,
+    pub new_state: Digest,
+    pub shots: Vec<Position>,
+    /// Number of shots that hit a ship, including shots that sunk a ship.
+    pub hits: u32,
+    /// Number of shots that sunk a ship.
+    pub sinks: u32,
+}
+
+impl DamageReportCommit {
+    /// Totals the results of the rounds from applying the given shots to the state with the given
+    /// commitment.
+    pub fn from_rounds(old_state: Digest, shots: Vec<Position>, rounds: &[RoundCommit]) -> Self {
+        let hits = rounds
+            .iter()
+            .filter(|round| matches!(round.hit, HitType::Hit | HitType::Sunk(_)))
+            .count() as u32;
+        let sinks = rounds
+            .iter()
+            .filter(|round| matches!(round.hit, HitType::Sunk(_)))
+            .count() as u32;
+        Self {
+            old_state,
+            new_state: rounds.last().map_or(old_state, |round| round.new_state),
+            shots,
+            hits,
+            sinks,
         }
     }
 }
 
-#[cfg(test)]
+/// Input to the consistency guest, which checks a board against the results observed by the
+/// other player, such as when resuming a game with a different prover.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ConsistencyInput {
+    pub state: GameState,
+    pub fog: FogBoard,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ConsistencyCommit {
+    /// Commitment to the board, which is valid for the default game rules.
+    pub state: Digest,
+    pub fog: FogBoard,
+    /// Whether the board could have produced every result on the fog board, as checked by
+    /// [FogBoard::is_consistent_with].
+    pub consistent: bool,
+}
+
+/// Input to the rematch guest, which checks a new board against the layout revealed in a previous
+/// game.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RematchInput {
+    pub state: GameState,
+    /// The [GameState::layout_digest] of the board from the previous game.
+    pub prior_layout: Digest,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RematchCommit {
+    /// Commitment to the new board, which is valid for the default game rules.
+    pub state: Digest,
+    pub prior_layout: Digest,
+    /// Whether the new board uses a different layout than the previous game.
+    pub is_fresh: bool,
+}
+
+/// Journal of the init guest, with a commitment to the validated board and the digest of the fleet
+/// it was validated against.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct InitCommit {
+    pub state: Digest,
+    pub fleet: Digest,
+    /// The [GameState::layout_fingerprint] of the board, if the coordinator requested one, such
+    /// that it can detect a layout reused across matches.
+    pub layout: Option<LayoutFingerprint>,
+    /// The [MatchContext::digest] of the match the board was committed for, if any.
+    pub context: Option<Digest>,
+}
+
+/// Fingerprint of a board layout, keyed by a secret the coordinator shares only with the player
+/// whose board it is. There are few enough valid layouts that an unkeyed digest can be reversed by
+/// trying them all, so the key keeps the layout hidden from the opponent, who also sees the
+/// journal.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LayoutFingerprint {
+    /// The digest of the key, for the coordinator to check that the player used the key it was
+    /// given.
+    pub key: Digest,
+    pub fingerprint: Digest,
+}
+
+impl InitCommit {
+    /// Checks that the board was committed for the agreed match context, so that a board proven
+    /// for another match cannot be reused in this one.
+    pub fn check_context(&self, expected: Option<&Digest>) -> Result<(), GameError> {
+        match self.context.as_ref() == expected {
+            true => Ok(()),
+            false => Err(GameError::ContextMismatch),
+        }
+    }
+
+    /// Checks that the board was validated against the agreed fleet, so that neither player can
+    /// secretly play with a weaker fleet.
+    pub fn check_fleet(&self, expected: &Fleet) -> Result<(), GameError> {
+        let expected = expected.digest();
+        match self.fleet == expected {
+            true => Ok(()),
+            false => Err(GameError::FleetMismatch {
+                expected,
+                actual: self.fleet,
+            }),
+        }
+    }
+}
+
+/// Input to the forbidden zone guest, which validates a board as the init guest does and also
+/// checks that no ship occupies a forbidden cell, as required by some tournament rules.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ForbiddenZoneInput {
+    pub state: GameState,
+    pub forbidden: CellMask,
+}
+
+/// Journal of the forbidden zone guest, binding the validated board to the cells it avoids.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ForbiddenZoneCommit {
+    pub state: Digest,
+    /// The [CellMask::digest] of the forbidden cells.
+    pub forbidden: Digest,
+}
+
+/// Input to the reveal guest, which proves that a board revealed at settlement matches the
+/// commitment made to it, such that a contract only has to compare digests.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RevealInput {
+    pub state: GameState,
+    /// Whether to also commit the [GameState::layout_digest], for public display of the layout.
+    pub disclose_layout: bool,
+}
+
+/// Journal of the reveal guest.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RevealCommit {
+    /// The [GameState::commit] of the revealed board, to be compared against the stored
+    /// commitment.
+    pub board_commit: Digest,
+    pub layout: Option<Digest>,
+}
+
+/// The context of a match agreed by both players before it starts. Its digest is committed by the
+/// init and round guests, binding each proof to the match.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MatchContext {
+    pub players: Vec<String>,
+    pub match_id: String,
+    /// When the match was arranged, in seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl MatchContext {
+    /// Digest identifying the match, committed in the journals in place of the full context.
+    pub fn digest(&self) -> Digest {
+        let bytes = canonical_bincode()
+            .serialize(&self)
+            .expect("context serialization should always succeed");
+        *risc0_zkvm::sha::Impl::hash_bytes(&bytes)
+    }
+}
+
+/// Commitments to the boards of both players in a match, established before the first shot.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MatchSetup {
+    pub player_a_commit: Digest,
+    pub player_b_commit: Digest,
+}
+
+impl MatchSetup {
+    /// Verifies the init receipt from each player and records their committed board states. Both
+    /// boards must have been validated against the same fleet.
+    pub fn from_init_receipts(
+        player_a: &Receipt,
+        player_b: &Receipt,
+        init_id: impl Into<Digest>,
+    ) -> anyhow::Result<Self> {
+        let (player_a, player_b) = Self::decode_init_receipts(player_a, player_b, init_id.into())?;
+        anyhow::ensure!(
+            player_a.fleet == player_b.fleet,
+            "players committed to boards for different fleets"
+        );
+        Ok(Self {
+            player_a_commit: player_a.state,
+            player_b_commit: player_b.state,
+        })
+    }
+
+    /// Verifies the init receipt from each player as in [MatchSetup::from_init_receipts], but for
+    /// handicap matches where each player has their own fleet. Each board must have been validated
+    /// against the fleet agreed for that player.
+    pub fn from_init_receipts_with_fleets(
+        player_a: (&Receipt, &Fleet),
+        player_b: (&Receipt, &Fleet),
+        init_id: impl Into<Digest>,
+    ) -> anyhow::Result<Self> {
+        let ((receipt_a, fleet_a), (receipt_b, fleet_b)) = (player_a, player_b);
+        let (player_a, player_b) =
+            Self::decode_init_receipts(receipt_a, receipt_b, init_id.into())?;
+        player_a
+            .check_fleet(fleet_a)
+            .context("player A committed to a board for a different fleet")?;
+        player_b
+            .check_fleet(fleet_b)
+            .context("player B committed to a board for a different fleet")?;
+        Ok(Self {
+            player_a_commit: player_a.state,
+            player_b_commit: player_b.state,
+        })
+    }
+
+    /// Verifies the init receipt from each player and decodes their journals.
+    fn decode_init_receipts(
+        player_a: &Receipt,
+        player_b: &Receipt,
+        init_id: Digest,
+    ) -> anyhow::Result<(InitCommit, InitCommit)> {
+        player_a
+            .verify(init_id)
+            .context("failed to verify init receipt for player A")?;
+        player_b
+            .verify(init_id)
+            .context("failed to verify init receipt for player B")?;
+        Ok((player_a.journal.decode()?, player_b.journal.decode()?))
+    }
+}
+
+impl Ship {
+    pub fn points(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.class.span()).map(|offset| self.pos.step(self.dir, offset))
+    }
+
+    /// Returns the bow and stern cells of the ship, without computing the cells in between.
+    pub fn endpoints(&self) -> (Position, Position) {
+        (self.pos, self.pos.step(self.dir, self.class.span() - 1))
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.points().any(|p| other.points().any(|q| p == q))
+    }
+
+    pub fn in_bounds(&self) -> bool {
+        self.pos.in_bounds() && self.pos.step(self.dir, self.class.span() - 1).in_bounds()
+    }
+
+    /// Checks the invariants of the ship on its own: it has a nonzero length, it is within the
+    /// bounds of the board, and its hit mask has no bits set beyond its length.
+    pub fn validate(&self) -> Result<(), GameError> {
+        if self.class.span() == 0 {
+            return Err(GameError::ZeroLength(self.class));
+        }
+        if !self.in_bounds() {
+            return Err(GameError::OutOfBounds(self.class));
+        }
+        if self.hit_mask & !self.class.sunk_mask() != 0 {
+            return Err(GameError::InvalidHitMask(self.class));
+        }
+        Ok(())
+    }
+
+    /// Points occupied by the ship under the given ruleset. On a toroidal board, ships that run
+    /// off one edge wrap around to the opposite edge.
+    pub fn points_with(&self, config: &GameConfig) -> Vec<Position> {
+        (0..self.class.span())
+            .map(|offset| match config.toroidal {
+                true => self.pos.step_wrapping(self.dir, offset, BOARD_SIZE as u32),
+                false => self.pos.step(self.dir, offset),
+            })
+            .collect()
+    }
+
+    /// Checks that the ship is in bounds under the given ruleset. On a toroidal board, only the
+    /// starting position needs to be on the board.
+    pub fn in_bounds_with(&self, config: &GameConfig) -> bool {
+        match config.toroidal {
+            true => self.pos.in_bounds(),
+            false => self.in_bounds(),
+        }
+    }
+}
+
+impl GameState {
+    pub fn new(pepper: [u8; PEPPER_LEN]) -> Self {
+        Self {
+            ships: Vec::new(),
+            pepper: pepper.to_vec(),
+        }
+    }
+
+    /// Creates a game state with the given ships, checking that it is valid under the default rules
+    /// as in [GameState::check], and returning the first problem found otherwise.
+    pub fn with_ships(ships: Vec<Ship>, pepper: [u8; PEPPER_LEN]) -> Result<Self, GameError> {
+        let state = Self {
+            ships,
+            pepper: pepper.to_vec(),
+        };
+        state.check_partial()?;
+        for ship in state.ships.iter() {
+            if ship.dir.is_diagonal() {
+                return Err(GameError::DiagonalNotAllowed(ship.class));
+            }
+        }
+        for class in ShipClass::list() {
+            if !state.ships.iter().any(|ship| ship.class == *class) {
+                return Err(GameError::MissingClass(*class));
+            }
+        }
+        Ok(state)
+    }
+
+    /// Creates an empty game state with a 16-byte pepper, as used before the default length was
+    /// increased to [PEPPER_LEN]. Commitments to such states match those of earlier versions.
+    pub fn new_16(pepper: [u8; 16]) -> Self {
+        Self {
+            ships: Vec::new(),
+            pepper: pepper.to_vec(),
+        }
+    }
+
+    /// Checks whether the game state contains a valid configuration of ships.
+    #[must_use]
+    pub fn check(&self) -> bool {
+        self.check_with(&GameConfig::default())
+    }
+
+    /// Checks whether the game state contains a valid configuration of ships under the ruleset
+    /// given by the config.
+    #[must_use]
+    pub fn check_with(&self, config: &GameConfig) -> bool {
+        if self.pepper.len() < MIN_PEPPER_LEN {
+            return false;
+        }
+
+        // Ensure every ship is in bounds, has no hits beyond its length, and is in an allowed
+        // direction.
+        for ship in self.ships.iter() {
+            if !ship.in_bounds_with(config) {
+                return false;
+            }
+            if ship.hit_mask & !ship.class.sunk_mask() != 0 {
+                return false;
+            }
+            if ship.dir.is_diagonal() && !config.allow_diagonal {
+                return false;
+            }
+        }
+
+        // Ensure the classes of the ships match the classes in the fleet exactly.
+        let mut classes = config.fleet.classes.clone();
+        for ship in self.ships.iter() {
+            let Some(class_index) = classes.iter().position(|class| ship.class == *class) else {
+                return false;
+            };
+            classes.swap_remove(class_index);
+        }
+        if !classes.is_empty() {
+            return false;
+        }
+
+        // Ensure no two ships are intersecting. Comparing each pair of ships is quadratic in the
+        // number of ships, so large custom fleets use a bitboard instead.
+        let points: Vec<Vec<Position>> = self
+            .ships
+            .iter()
+            .map(|ship| ship.points_with(config))
+            .collect();
+        match points.len() >= BITBOARD_MIN_SHIPS {
+            true => !overlaps_bitboard(&points),
+            false => !overlaps_pairwise(&points),
+        }
+    }
+
+    /// Checks whether both game states are valid, and that no ship in one overlaps with a ship in
+    /// the other, such that both fleets can be placed in a single shared ocean.
+    #[must_use]
+    pub fn check_shared_ocean(&self, other: &Self) -> bool {
+        if !self.check() || !other.check() {
+            return false;
+        }
+        !self.ships.iter().any(|ship| {
+            other
+                .ships
+                .iter()
+                .any(|other_ship| ship.intersects(other_ship))
+        })
+    }
+
+    /// Checks whether the ships placed so far are valid, allowing for a board that does not yet
+    /// have every ship in the fleet, such as during interactive placement. Use [GameState::check]
+    /// to validate a complete board.
+    pub fn check_partial(&self) -> Result<(), GameError> {
+        if self.pepper.len() < MIN_PEPPER_LEN {
+            return Err(GameError::PepperTooShort(self.pepper.len()));
+        }
+        for (i, ship_i) in self.ships.iter().enumerate() {
+            ship_i.validate()?;
+            for ship_j in self.ships.iter().skip(i + 1) {
+                if ship_i.class == ship_j.class {
+                    return Err(GameError::DuplicateClass(ship_i.class));
+                }
+                if ship_i.intersects(ship_j) {
+                    return Err(GameError::Overlap(ship_i.class, ship_j.class));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn add(&mut self, new_ship: Ship) -> bool {
+        self.try_add(new_ship).is_ok()
+    }
+
+    /// Adds the ship to the board, or returns the reason it cannot be placed: it is out of bounds,
+    /// a ship of its class is already on the board, or it overlaps another ship.
+    pub fn try_add(&mut self, new_ship: Ship) -> Result<(), GameError> {
+        if !new_ship.in_bounds() {
+            return Err(GameError::OutOfBounds(new_ship.class));
+        }
+
+        // Ensure that there is not already a ship with that class in the state.
+        for ship in self.ships.iter() {
+            if ship.class == new_ship.class {
+                return Err(GameError::DuplicateClass(new_ship.class));
+            }
+            if ship.intersects(&new_ship) {
+                return Err(GameError::Overlap(ship.class, new_ship.class));
+            }
+        }
+
+        self.ships.push(new_ship);
+        Ok(())
+    }
+
+    pub fn apply_shot(&mut self, shot: impl Into<Position>) -> HitType {
+        self.apply_shot_with(shot, SinkRule::AnyOrder)
+    }
+
+    /// Applies the shot as in [GameState::apply_shot], counting hits according to the sink rule.
+    pub fn apply_shot_with(&mut self, shot: impl Into<Position>, rule: SinkRule) -> HitType {
+        let shot = shot.into();
+        for ship in self.ships.iter_mut() {
+            let hit = ship.apply_shot_with(shot, rule);
+            match hit {
+                HitType::Miss => continue,
+                _ => return hit,
+            }
+        }
+        HitType::Miss
+    }
+
+    /// Returns the result [GameState::apply_shot] would give for the shot, without changing the
+    /// state, e.g. to cross-check the result claimed by a proof when the board is known.
+    pub fn peek_shot(&self, shot: Position) -> HitType {
+        self.clone().apply_shot(shot)
+    }
+
+    /// Applies each shot in order, as with [GameState::apply_shot], and returns their results.
+    pub fn apply_shots(&mut self, shots: &[Position]) -> Vec<HitType> {
+        shots.iter().map(|&shot| self.apply_shot(shot)).collect()
+    }
+
+    /// Returns the state after applying each shot in order, leaving this state unchanged.
+    pub fn apply_shots_pure(&self, shots: &[Position]) -> Self {
+        let mut state = self.clone();
+        state.apply_shots(shots);
+        state
+    }
+
+    /// Checks that the shot is on the board and, if strict, that it does not target a ship that
+    /// is already sunk, as in [GameState::apply_shot_strict].
+    pub fn check_shot(&self, shot: Position, strict: bool) -> Result<(), GameError> {
+        if !shot.in_bounds() {
+            return Err(GameError::ShotOutOfBounds(shot));
+        }
+        if strict && self.ship_at(shot).is_some_and(|(_, ship)| ship.is_sunk()) {
+            return Err(GameError::ShotAtSunkShip(shot));
+        }
+        Ok(())
+    }
+
+    /// Applies the shot as in [GameState::apply_shot], except under rulesets where sunk ships are
+    /// revealed, such that firing at a cell of an already sunk ship is not allowed. In that case,
+    /// [HitType::Invalid] is returned and the state is unchanged.
+    pub fn apply_shot_strict(&mut self, shot: impl Into<Position>) -> HitType {
+        let shot = shot.into();
+        let targets_sunk_ship = self.ship_at(shot).is_some_and(|(_, ship)| ship.is_sunk());
+        match targets_sunk_ship {
+            true => HitType::Invalid,
+            false => self.apply_shot(shot),
+        }
+    }
+
+    /// Puts the state into a normalized form, with the ships sorted in the order of
+    /// [ShipClass::list] and any bits of each hit mask beyond the span of the ship cleared.
+    pub fn canonicalize(&mut self) {
+        self.ships.sort_by_key(|ship| ship.class.index());
+        for ship in self.ships.iter_mut() {
+            ship.hit_mask &= ship.class.sunk_mask();
+        }
+    }
+
+    /// Returns a copy of the state in the normalized form produced by [GameState::canonicalize].
+    pub fn canonical(&self) -> Self {
+        let mut state = self.clone();
+        state.canonicalize();
+        state
+    }
+
+    /// Compares the ship placements and hits of both boards, ignoring the pepper and any
+    /// differences removed by [GameState::canonicalize]. Use `==` to also compare the pepper.
+    pub fn same_board(&self, other: &Self) -> bool {
+        self.canonical().ships == other.canonical().ships
+    }
+
+    /// Returns the index and a reference to the ship occupying the given position, if any.
+    pub fn ship_at(&self, pos: Position) -> Option<(usize, &Ship)> {
+        self.ships
+            .iter()
+            .enumerate()
+            .find(|(_, ship)| ship.points().any(|p| p == pos))
+    }
+
+    /// Returns true if a ship occupies any cell orthogonally adjacent to the given position.
+    pub fn adjacent_to_ship(&self, pos: Position) -> bool {
+        pos.neighbors().any(|p| self.ship_at(p).is_some())
+    }
+
+    /// Number of cells on the board occupied by at least one ship.
+    pub fn occupied_count(&self) -> usize {
+        Position::all()
+            .filter(|&pos| self.ship_at(pos).is_some())
+            .count()
+    }
+
+    /// Number of cells on the board not occupied by any ship.
+    pub fn empty_count(&self) -> usize {
+        BOARD_SIZE * BOARD_SIZE - self.occupied_count()
+    }
+
+    /// Returns the minimum and maximum corners of the smallest box containing every cell occupied
+    /// by a ship, or `None` if there are no ships on the board.
+    pub fn bounding_box(&self) -> Option<(Position, Position)> {
+        let mut points = self.ships.iter().flat_map(Ship::points);
+        let first = points.next()?;
+        Some(points.fold((first, first), |(min, max), p| {
+            (
+                Position::from((min.x.min(p.x), min.y.min(p.y))),
+                Position::from((max.x.max(p.x), max.y.max(p.y))),
+            )
+        }))
+    }
+
+    /// Returns the classes of the ships that have not yet been sunk, in the order of
+    /// [ShipClass::list] regardless of the order of the ships.
+    pub fn remaining_classes(&self) -> Vec<ShipClass> {
+        let mut classes: Vec<ShipClass> = self
+            .ships
+            .iter()
+            .filter(|ship| !ship.is_sunk())
+            .map(|ship| ship.class)
+            .collect();
+        classes.sort_by_key(|class| class.index());
+        classes
+    }
+
+    /// Returns the ships that have been sunk, in the order of their classes in [ShipClass::list]
+    /// regardless of the order of the ships.
+    pub fn sunk_ships(&self) -> Vec<&Ship> {
+        let mut ships: Vec<&Ship> = self.ships.iter().filter(|ship| ship.is_sunk()).collect();
+        ships.sort_by_key(|ship| ship.class.index());
+        ships
+    }
+
+    /// Returns every cell of a ship that has been hit, in the order of the ships and from bow to
+    /// stern within each ship. Misses are not recorded in the state, so are not included.
+    pub fn hit_positions(&self) -> Vec<Position> {
+        self.ships.iter().flat_map(Ship::hit_points).collect()
+    }
+
+    /// Total number of cells of the ships that have been hit.
+    pub fn damage_taken(&self) -> u32 {
+        self.ships
+            .iter()
+            .map(|ship| (ship.hit_mask & ship.class.sunk_mask()).count_ones())
+            .sum()
+    }
+
+    /// Returns the class of each ship with its remaining health, sorted by ascending health so that
+    /// the ship closest to sinking comes first. Sunk ships are included with a health of zero, and
+    /// ties keep the order of the ships on the board.
+    pub fn ships_by_damage(&self) -> Vec<(ShipClass, u32)> {
+        let mut ships: Vec<(ShipClass, u32)> = self
+            .ships
+            .iter()
+            .map(|ship| (ship.class, ship.remaining_health()))
+            .collect();
+        ships.sort_by_key(|(_, health)| *health);
+        ships
+    }
+
+    /// Returns true if every ship on the board has been sunk.
+    pub fn is_defeated(&self) -> bool {
+        self.ships.iter().all(Ship::is_sunk)
+    }
+
+    /// Applies the shot and returns the commit for the round, binding the state before and after
+    /// the shot to its result.
+    ///
+    /// Returns [GameError::ShotOutOfBounds] without changing the state if the shot is off the
+    /// board.
+    pub fn play_round(&mut self, shot: Position) -> Result<RoundCommit, GameError> {
+        let cell = CellIndex::try_from(shot)?;
+        let (mut opening, old_state) = self.open_cell(cell);
+        let target = self
+            .ship_at(shot)
+            .map(|(index, ship)| (index, ship.hit_mask));
+        let hit = self.apply_shot(shot);
+
+        // The board only changes when the shot hits a cell that was not hit before. Misses and
+        // repeated hits leave it as it was, so the commitments to it are reused rather than
+        // serializing and hashing the board again, which dominates the cost of the round. Only the
+        // cell under the shot changes, so the new cell root is recomputed from its opening.
+        let changed = match target {
+            Some((index, old_hit_mask)) => self.ships[index].hit_mask != old_hit_mask,
+            None => false,
+        };
+        let new_state = match changed {
+            true => {
+                opening.new_board = self.board_digest();
+                let cell_root = opening.root(cell, self.cell_state(shot));
+                merkle::state_commit(&opening.new_board, &cell_root)
+            }
+            false => old_state,
+        };
+        Ok(RoundCommit {
+            old_state,
+            new_state,
+            shot: cell,
+            sunk_cells: self.sunk_cells(&hit),
+            hit,
+            game_over: self.is_defeated(),
+            opening,
+            context: None,
+        })
+    }
+
+    /// Returns the cells of the ship sunk by a shot with the given result, or `None` if the result
+    /// is not [HitType::Sunk].
+    pub fn sunk_cells(&self, hit: &HitType) -> Option<Vec<Position>> {
+        let HitType::Sunk(class) = hit else {
+            return None;
+        };
+        self.ship_cells(*class)
+    }
+
+    /// Returns every cell of the ship of the given class, which are the cells that must be hit to
+    /// sink it, or `None` if there is no such ship on the board.
+    pub fn ship_cells(&self, class: ShipClass) -> Option<Vec<Position>> {
+        self.ships
+            .iter()
+            .find(|ship| ship.class == class)
+            .map(|ship| ship.points().collect())
+    }
+
+    /// Serializes the game state using a pinned encoding, such that the same state always results
+    /// in the same bytes regardless of platform or the defaults of the bincode version in use.
+    ///
+    /// A 16-byte pepper is encoded as a fixed-size array without a length prefix, as before the
+    /// pepper became variable length, so that the board digests of such states are unchanged.
+    /// This relies on [GameState::check] rejecting peppers shorter than [MIN_PEPPER_LEN]: the
+    /// ships take a fixed number of bytes each, and a longer pepper with its 8-byte prefix always
+    /// takes more than 16 bytes, but an 8-byte pepper with its prefix takes exactly 16 bytes and
+    /// can collide.
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        let bytes = match <[u8; 16]>::try_from(self.pepper.as_slice()) {
+            Ok(pepper) => canonical_bincode().serialize(&(&self.ships, pepper)),
+            Err(_) => canonical_bincode().serialize(&self),
+        };
+        bytes.expect("state serialization should always succeed")
+    }
+
+    /// Commitment to the state, binding the [GameState::board_digest] to the
+    /// [GameState::cell_root], such that each round can open the cell under its shot.
+    pub fn commit(&self) -> Digest {
+        merkle::state_commit(&self.board_digest(), &self.cell_root())
+    }
+
+    /// Returns the board reflected left to right, for variants where both players use mirrored
+    /// setups. The pepper and the hits on each ship are kept.
+    pub fn mirror_horizontal(&self) -> Self {
+        let max = BOARD_SIZE as u32 - 1;
+        self.transformed(|pos| Position {
+            x: max.wrapping_sub(pos.x),
+            y: pos.y,
+        })
+    }
+
+    /// Returns the board reflected top to bottom, keeping the pepper and the hits on each ship.
+    pub fn mirror_vertical(&self) -> Self {
+        let max = BOARD_SIZE as u32 - 1;
+        self.transformed(|pos| Position {
+            x: pos.x,
+            y: max.wrapping_sub(pos.y),
+        })
+    }
+
+    /// Returns the board rotated a quarter turn clockwise, keeping the pepper and the hits on each
+    /// ship.
+    pub fn rotate_90(&self) -> Self {
+        let max = BOARD_SIZE as u32 - 1;
+        self.transformed(|pos| Position {
+            x: max.wrapping_sub(pos.y),
+            y: pos.x,
+        })
+    }
+
+    /// Maps every cell of every ship through the given symmetry of the board. A valid board stays
+    /// valid, since each ship is still a line of cells on the board.
+    fn transformed(&self, map: impl Fn(Position) -> Position) -> Self {
+        Self {
+            ships: self
+                .ships
+                .iter()
+                .map(|ship| ship.transformed(&map))
+                .collect(),
+            pepper: self.pepper.clone(),
+        }
+    }
+
+    /// Digest of the ship placements alone, ignoring the pepper and any hits. Unlike
+    /// [GameState::commit], this does not hide the board, and is intended for comparing a new
+    /// board against one that was revealed in a previous game.
+    pub fn layout_digest(&self) -> Digest {
+        *risc0_zkvm::sha::Impl::hash_bytes(&self.layout_bytes())
+    }
+
+    /// Fingerprint of the ship placements, keyed by a secret known only to the player and the
+    /// coordinator. Like [GameState::layout_digest] it ignores the pepper and any hits, but it
+    /// cannot be reversed without the key.
+    pub fn layout_fingerprint(&self, key: &Digest) -> LayoutFingerprint {
+        let keyed = [key.as_bytes(), &self.layout_bytes()].concat();
+        LayoutFingerprint {
+            key: *risc0_zkvm::sha::Impl::hash_bytes(key.as_bytes()),
+            fingerprint: *risc0_zkvm::sha::Impl::hash_bytes(&keyed),
+        }
+    }
+
+    fn layout_bytes(&self) -> Vec<u8> {
+        let mut ships = self.canonical().ships;
+        for ship in ships.iter_mut() {
+            ship.hit_mask = 0;
+        }
+        canonical_bincode()
+            .serialize(&ships)
+            .expect("ship serialization should always succeed")
+    }
+
+    /// Returns the commitment to this state as eight little-endian words, matching the word
+    /// layout of [Digest], for storage by settlement contracts. Use [settlement_to_digest] to
+    /// convert back.
+    pub fn settlement_digest(&self) -> [u32; 8] {
+        let digest = self.commit();
+        let bytes = digest.as_bytes();
+        core::array::from_fn(|i| u32::from_le_bytes(core::array::from_fn(|j| bytes[i * 4 + j])))
+    }
+}
+
+/// Returns true if any cell appears in the points of more than one ship.
+fn overlaps_pairwise(points: &[Vec<Position>]) -> bool {
+    points.iter().enumerate().any(|(i, points_i)| {
+        points
+            .iter()
+            .skip(i + 1)
+            .any(|points_j| points_i.iter().any(|p| points_j.contains(p)))
+    })
+}
+
+/// Returns true if any cell appears in the points of more than one ship, by comparing the number of
+/// occupied cells against the total span of the ships. Every point must be on the board.
+fn overlaps_bitboard(points: &[Vec<Position>]) -> bool {
+    let mut occupied = CellMask::new();
+    let mut span = 0;
+    for &pos in points.iter().flatten() {
+        occupied.insert(pos);
+        span += 1;
+    }
+    occupied.count() != span
+}
+
+/// Checks that every shot in the sequence is in bounds and that no shot is repeated, returning the
+/// error for the first shot that is not.
+pub fn validate_shot_sequence(shots: &[Position]) -> Result<(), GameError> {
+    let mut seen = [false; BOARD_SIZE * BOARD_SIZE];
+    for &shot in shots {
+        let index = shot.to_index().ok_or(GameError::ShotOutOfBounds(shot))? as usize;
+        if seen[index] {
+            return Err(GameError::DuplicateShot(shot));
+        }
+        seen[index] = true;
+    }
+    Ok(())
+}
+
+/// Reconstructs the [Digest] from words returned by [GameState::settlement_digest].
+pub fn settlement_to_digest(words: [u32; 8]) -> Digest {
+    Digest::from_bytes(core::array::from_fn(|i| words[i / 4].to_le_bytes()[i % 4]))
+}
+
+/// Bincode options used for all committed encodings: fixed-width integers in little-endian order.
+fn canonical_bincode() -> impl bincode::Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_little_endian()
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<GameState> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GameState {
+        let state = GameState::sample_with(rng, &Fleet::standard(), MAX_SAMPLE_ATTEMPTS)
+            .unwrap_or_else(|err| panic!("failed to sample a game state: {}", err));
+
+        // The resulting state should always be valid.
+        if !state.check() {
+            panic!("state is invalid: {:?}", state);
+        }
+        state
+    }
+}
+
+#[cfg(feature = "rand")]
+impl GameState {
+    /// Samples a random placement of the given fleet, retrying up to the given number of times if
+    /// the ships placed so far leave no room for the next one.
+    pub fn sample_with<R: Rng + ?Sized>(
+        rng: &mut R,
+        fleet: &Fleet,
+        max_attempts: u32,
+    ) -> Result<Self, GenError> {
+        let pepper = rng.random::<[u8; PEPPER_LEN]>().to_vec();
+        for _ in 0..max_attempts {
+            if let Some(ships) = Self::sample_ships(rng, fleet) {
+                return Ok(Self {
+                    ships,
+                    pepper: pepper.clone(),
+                });
+            }
+        }
+        Err(GenError::AttemptsExhausted(max_attempts))
+    }
+
+    fn sample_ships<R: Rng + ?Sized>(rng: &mut R, fleet: &Fleet) -> Option<Vec<Ship>> {
+        // Create a shuffled list of all positions on the board.
+        let mut positions: Vec<Position> = Position::all().collect();
+        positions.shuffle(rng);
+
+        // Place the ships in the order of the fleet, using the shuffled positions.
+        let mut ships = Vec::<Ship>::new();
+        for ship_class in fleet.classes.iter() {
+            let ship = positions.iter().find_map(|pos| {
+                let dir: Direction = rng.random();
+                [dir, dir.flip()]
+                    .into_iter()
+                    .map(|dir| Ship::new(*ship_class, *pos, dir))
+                    .find(|ship| ship.in_bounds() && !ships.iter().any(|s| s.intersects(ship)))
+            })?;
+            ships.push(ship);
+        }
+        Some(ships)
+    }
+}
+
+impl Position {
+    pub fn step(self, dir: Direction, dist: u32) -> Self {
+        match dir {
+            Direction::Vertical => Self {
+                x: self.x,
+                y: self.y + dist,
+            },
+            Direction::Horizontal => Self {
+                x: self.x + dist,
+                y: self.y,
+            },
+            Direction::DiagonalDown => Self {
+                x: self.x + dist,
+                y: self.y + dist,
+            },
+            // Stepping up past the top of the board wraps, resulting in an out of bounds position.
+            Direction::DiagonalUp => Self {
+                x: self.x + dist,
+                y: self.y.wrapping_sub(dist),
+            },
+        }
+    }
+
+    /// Iterates over the orthogonally adjacent positions that are on the board.
+    pub fn neighbors(self) -> impl Iterator<Item = Position> {
+        [
+            (self.x.wrapping_sub(1), self.y),
+            (self.x + 1, self.y),
+            (self.x, self.y.wrapping_sub(1)),
+            (self.x, self.y + 1),
+        ]
+        .into_iter()
+        .map(Position::from)
+        .filter(Position::in_bounds)
+    }
+
+    /// Steps as in [Position::step], but wraps coordinates modulo the given board size, as on a
+    /// toroidal board. Panics if the size is zero.
+    pub fn step_wrapping(self, dir: Direction, dist: u32, size: u32) -> Self {
+        let (x, y, dist) = (self.x % size, self.y % size, dist % size);
+        match dir {
+            Direction::Vertical => Self {
+                x,
+                y: (y + dist) % size,
+            },
+            Direction::Horizontal => Self {
+                x: (x + dist) % size,
+                y,
+            },
+            Direction::DiagonalDown => Self {
+                x: (x + dist) % size,
+                y: (y + dist) % size,
+            },
+            Direction::DiagonalUp => Self {
+                x: (x + dist) % size,
+                y: (y + size - dist) % size,
+            },
+        }
+    }
+
+    /// Clamps each coordinate to a board of the given size. A board of size zero has no cells,
+    /// so every position clamps to the origin.
+    pub fn clamp(self, size: u32) -> Self {
+        let max = size.saturating_sub(1);
+        Self {
+            x: self.x.min(max),
+            y: self.y.min(max),
+        }
+    }
+
+    /// Check that the [Position] is within the bounds of the board.
+    #[must_use]
+    pub fn in_bounds(&self) -> bool {
+        self.x < BOARD_SIZE as u32 && self.y < BOARD_SIZE as u32
+    }
+
+    /// Creates the position, or returns [GameError::ShotOutOfBounds] if it is not on the board.
+    /// Prefer this to the infallible `From<(u32, u32)>` for coordinates from untrusted input.
+    pub fn try_new(x: u32, y: u32) -> Result<Self, GameError> {
+        let pos = Self { x, y };
+        match pos.in_bounds() {
+            true => Ok(pos),
+            false => Err(GameError::ShotOutOfBounds(pos)),
+        }
+    }
+
+    /// Iterates in row-major order over the positions within the inclusive rectangle spanned by
+    /// the two corners, clipped to the bounds of the board.
+    pub fn range(
+        top_left: impl Into<Position>,
+        bottom_right: impl Into<Position>,
+    ) -> impl Iterator<Item = Position> {
+        let top_left = top_left.into();
+        let bottom_right = bottom_right.into();
+        let max = BOARD_SIZE as u32 - 1;
+        (top_left.y..=bottom_right.y.min(max)).flat_map(move |y| {
+            (top_left.x..=bottom_right.x.min(max)).map(move |x| Position { x, y })
+        })
+    }
+
+    /// Iterates in row-major order over every position on the board.
+    pub fn all() -> impl Iterator<Item = Position> {
+        Self::range((0, 0), (BOARD_SIZE as u32 - 1, BOARD_SIZE as u32 - 1))
+    }
+
+    /// Encodes the position as a single row-major cell index, for compact transmission. Returns
+    /// `None` if the position is not on the board.
+    pub fn to_index(&self) -> Option<u8> {
+        self.in_bounds()
+            .then(|| (self.y * BOARD_SIZE as u32 + self.x) as u8)
+    }
+
+    /// Decodes a position from a cell index produced by [Position::to_index], returning `None` if
+    /// the index is not on the board.
+    pub fn from_index(index: u8) -> Option<Self> {
+        let index = index as u32;
+        let size = BOARD_SIZE as u32;
+        (index < size * size).then(|| Self {
+            x: index % size,
+            y: index / size,
+        })
+    }
+}
+
+impl From<(u32, u32)> for Position {
+    fn from(value: (u32, u32)) -> Self {
+        Self {
+            x: value.0,
+            y: value.1,
+        }
+    }
+}
+
+impl CellIndex {
+    pub fn index(self) -> u8 {
+        self.0
+    }
+
+    pub fn position(self) -> Position {
+        self.into()
+    }
+}
+
+impl TryFrom<u8> for CellIndex {
+    type Error = GameError;
+
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        match Position::from_index(index) {
+            Some(_) => Ok(Self(index)),
+            None => Err(GameError::InvalidCellIndex(index)),
+        }
+    }
+}
+
+impl From<CellIndex> for u8 {
+    fn from(cell: CellIndex) -> Self {
+        cell.0
+    }
+}
+
+impl TryFrom<Position> for CellIndex {
+    type Error = GameError;
+
+    fn try_from(pos: Position) -> Result<Self, Self::Error> {
+        pos.to_index()
+            .map(Self)
+            .ok_or(GameError::ShotOutOfBounds(pos))
+    }
+}
+
+impl From<CellIndex> for Position {
+    fn from(cell: CellIndex) -> Self {
+        Position::from_index(cell.0).expect("cell index should always be on the board")
+    }
+}
+
+impl PartialEq<Position> for CellIndex {
+    fn eq(&self, other: &Position) -> bool {
+        self.position() == *other
+    }
+}
+
+impl Display for CellIndex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.position().fmt(f)
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Direction {
+    /// Swaps horizontal with vertical, and the downward diagonal with the upward diagonal.
+    pub fn flip(self) -> Self {
+        match self {
+            Self::Horizontal => Self::Vertical,
+            Self::Vertical => Self::Horizontal,
+            Self::DiagonalDown => Self::DiagonalUp,
+            Self::DiagonalUp => Self::DiagonalDown,
+        }
+    }
+
+    /// Cycles through each of the directions allowed by the ruleset, e.g. for a placement UI.
+    pub fn cycle(self, allow_diagonal: bool) -> Self {
+        match (self, allow_diagonal) {
+            (Self::Horizontal, _) => Self::Vertical,
+            (Self::Vertical, true) => Self::DiagonalDown,
+            (Self::DiagonalDown, true) => Self::DiagonalUp,
+            (Self::Vertical | Self::DiagonalDown | Self::DiagonalUp, _) => Self::Horizontal,
+        }
+    }
+
+    /// Returns true for the diagonal directions, which are not allowed by the standard rules.
+    pub fn is_diagonal(self) -> bool {
+        matches!(self, Self::DiagonalDown | Self::DiagonalUp)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<Direction> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Direction {
+        match rng.random::<bool>() {
+            true => Direction::Horizontal,
+            false => Direction::Vertical,
+        }
+    }
+}
+
+impl Ship {
+    pub fn new(class: ShipClass, pos: impl Into<Position>, dir: Direction) -> Self {
+        Ship {
+            class,
+            pos: pos.into(),
+            dir,
+            hit_mask: 0,
+        }
+    }
+
+    pub fn with_hit_mask(self, hit_mask: u8) -> Self {
+        Self { hit_mask, ..self }
+    }
+
+    /// Packs the ship into the low 18 bits of a `u32`: 3 bits of class index, 4 bits each of x
+    /// and y, 2 bits of direction, and 5 bits of hit mask, from least to most significant. Returns
+    /// `None` if the position is off the board or the hit mask has bits beyond the length of the
+    /// ship, which [Ship::unpack] would reject, rather than truncating them.
+    pub fn pack(&self) -> Option<u32> {
+        if !self.pos.in_bounds() || self.hit_mask & !self.class.sunk_mask() != 0 {
+            return None;
+        }
+        let dir = match self.dir {
+            Direction::Horizontal => 0,
+            Direction::Vertical => 1,
+            Direction::DiagonalDown => 2,
+            Direction::DiagonalUp => 3,
+        };
+        Some(
+            self.class.index() as u32
+                | (self.pos.x << 3)
+                | (self.pos.y << 7)
+                | (dir << 11)
+                | ((self.hit_mask as u32) << 13),
+        )
+    }
+
+    /// Inverse of [Ship::pack]. Returns `None` if the class index is invalid, the position is off
+    /// the board, the hit mask has bits beyond the length of the ship, or any unused bit is set.
+    pub fn unpack(packed: u32) -> Option<Self> {
+        if packed >> 18 != 0 {
+            return None;
+        }
+        let class = *ShipClass::list().get((packed & 0x7) as usize)?;
+        let pos = Position {
+            x: (packed >> 3) & 0xf,
+            y: (packed >> 7) & 0xf,
+        };
+        let dir = match (packed >> 11) & 0x3 {
+            0 => Direction::Horizontal,
+            1 => Direction::Vertical,
+            2 => Direction::DiagonalDown,
+            _ => Direction::DiagonalUp,
+        };
+        let hit_mask = ((packed >> 13) & 0x1f) as u8;
+        if !pos.in_bounds() || hit_mask & !class.sunk_mask() != 0 {
+            return None;
+        }
+        Some(Ship::new(class, pos, dir).with_hit_mask(hit_mask))
+    }
+
+    /// Returns the ship rotated about its anchor position, such that horizontal ships become
+    /// vertical and vice versa. Note that the result may not be in bounds.
+    pub fn rotated(self) -> Self {
+        Self {
+            dir: self.dir.flip(),
+            ..self
+        }
+    }
+
+    /// Returns the ship moved by the given offset, or `None` if any part of the resulting ship
+    /// would be off the board.
+    pub fn translated(self, dx: i32, dy: i32) -> Option<Self> {
+        let pos = Position {
+            x: self.pos.x.checked_add_signed(dx)?,
+            y: self.pos.y.checked_add_signed(dy)?,
+        };
+        let ship = Self { pos, ..self };
+        ship.in_bounds().then_some(ship)
+    }
+
+    /// Returns the ship with each of its cells mapped through a symmetry of the board. The
+    /// anchor and direction are chosen so that the ship runs right or down, as ships are placed,
+    /// and the hit mask is reversed if that puts the bow at the other end.
+    fn transformed(&self, map: impl Fn(Position) -> Position) -> Self {
+        let (bow, stern) = self.endpoints();
+        let (bow, stern) = (map(bow), map(stern));
+        let reversed = stern.x < bow.x || (stern.x == bow.x && stern.y < bow.y);
+        let (pos, end) = match reversed {
+            true => (stern, bow),
+            false => (bow, stern),
+        };
+        let dir = match (pos.x == end.x, pos.y == end.y, end.y > pos.y) {
+            (true, _, _) => Direction::Vertical,
+            (false, true, _) => Direction::Horizontal,
+            (false, false, true) => Direction::DiagonalDown,
+            (false, false, false) => Direction::DiagonalUp,
+        };
+        let span = self.class.span();
+        let hit_mask = match reversed {
+            true => self.hit_mask.reverse_bits() >> (8 - span),
+            false => self.hit_mask,
+        };
+        Ship::new(self.class, pos, dir).with_hit_mask(hit_mask)
+    }
+
+    /// Iterates over the cells of the ship that have been hit, from bow to stern.
+    pub fn hit_points(&self) -> impl Iterator<Item = Position> + '_ {
+        self.points()
+            .enumerate()
+            .filter(|(i, _)| self.hit_mask & (1 << i) != 0)
+            .map(|(_, pos)| pos)
+    }
+
+    /// Number of cells of the ship that have not been hit.
+    pub fn remaining_health(&self) -> u32 {
+        self.class.span() - (self.hit_mask & self.class.sunk_mask()).count_ones()
+    }
+
+    /// Returns true if every cell occupied by the ship has been hit.
+    pub fn is_sunk(&self) -> bool {
+        self.hit_mask == self.class.sunk_mask()
+    }
+
+    pub fn apply_shot(&mut self, shot: Position) -> HitType {
+        let hit_index = self.points().position(|pos| pos == shot);
+        match hit_index {
+            Some(hit_index) => {
+                self.hit_mask |= 1 << hit_index;
+                match self.is_sunk() {
+                    true => HitType::Sunk(self.class),
+                    false => HitType::Hit,
+                }
+            }
+            None => HitType::Miss,
+        }
+    }
+
+    /// Applies the shot as in [Ship::apply_shot], except that under [SinkRule::InOrder] a shot at
+    /// a segment is [HitType::Deflected] unless every segment before it has been hit.
+    pub fn apply_shot_with(&mut self, shot: Position, rule: SinkRule) -> HitType {
+        let hit_index = self.points().position(|pos| pos == shot);
+        match (rule, hit_index) {
+            (SinkRule::InOrder, Some(hit_index)) => {
+                let ahead_mask = (1u8 << hit_index) - 1;
+                match self.hit_mask & ahead_mask == ahead_mask {
+                    true => self.apply_shot(shot),
+                    false => HitType::Deflected,
+                }
+            }
+            _ => self.apply_shot(shot),
+        }
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{example_ships, example_state};
+
+    #[test]
+    fn basic() {
+        // Board
+        //  | 0 1 2 3 4 5 6 7 8 9 |
+        // 0|                     |
+        // 1|       BBBB          |
+        // 2|                     |
+        // 3|     A               |
+        // 4|     A               |
+        // 5|     A         SSS   |
+        // 6|     A               |
+        // 7|     A   C     DD    |
+        // 8|         C           |
+        // 9|         C           |
+        let state = example_state();
+
+        assert!(state.check());
+    }
+
+    #[test]
+    fn overlap() {
+        // Board
+        //  | 0 1 2 3 4 5 6 7 8 9 |
+        // 0|                     |
+        // 1|       BBBB          |
+        // 2|     C               |
+        // 3|     *               |
+        // 4|     *               |
+        // 5|     A         SSS   |
+        // 6|     A               |
+        // 7|     A         DD    |
+        // 8|                     |
+        // 9|                     |
+        let state = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
+                Ship::new(ShipClass::Cruiser, (2, 3), Direction::Vertical),
+                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+            ],
+            ..GameState::new(rand::random())
+        };
+
+        assert!(!state.check());
+    }
+
+    #[test]
+    fn validate_ship() {
+        let ship = Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal);
+        assert_eq!(ship.clone().with_hit_mask(0x03).validate(), Ok(()));
+        assert_eq!(
+            ship.clone().with_hit_mask(0x04).validate(),
+            Err(GameError::InvalidHitMask(ShipClass::Destroyer))
+        );
+        assert_eq!(
+            Ship::new(ShipClass::Destroyer, (9, 7), Direction::Horizontal).validate(),
+            Err(GameError::OutOfBounds(ShipClass::Destroyer))
+        );
+
+        // A board with an over-range hit mask is rejected by both checks.
+        let mut state = example_state();
+        state.ships[4] = ship.with_hit_mask(0xfc);
+        assert_eq!(
+            state.check_partial(),
+            Err(GameError::InvalidHitMask(ShipClass::Destroyer))
+        );
+        assert!(!state.check());
+    }
+
+    #[test]
+    fn try_add() {
+        let mut state = GameState::new(rand::random());
+        let carrier = Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical);
+        assert_eq!(state.try_add(carrier.clone()), Ok(()));
+
+        assert_eq!(
+            state.try_add(Ship::new(
+                ShipClass::Battleship,
+                (8, 1),
+                Direction::Horizontal
+            )),
+            Err(GameError::OutOfBounds(ShipClass::Battleship))
+        );
+        assert_eq!(
+            state.try_add(Ship::new(ShipClass::Carrier, (5, 0), Direction::Vertical)),
+            Err(GameError::DuplicateClass(ShipClass::Carrier))
+        );
+        assert_eq!(
+            state.try_add(Ship::new(ShipClass::Cruiser, (1, 5), Direction::Horizontal)),
+            Err(GameError::Overlap(ShipClass::Carrier, ShipClass::Cruiser))
+        );
+
+        // Rejected ships are not added, and add reports the same result as a bool.
+        assert_eq!(state.ships, vec![carrier]);
+        assert!(!state.add(Ship::new(ShipClass::Cruiser, (1, 5), Direction::Horizontal)));
+        assert!(state.add(Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical)));
+    }
+
+    #[test]
+    fn rounds() {
+        // Board
+        //  | 0 1 2 3 4 5 6 7 8 9 |
+        // 0|                     |
+        // 1|       B B B B       |
+        // 2|                     |
+        // 3|     A               |
+        // 4|     A               |
+        // 5|     A         D D D |
+        // 6|     A               |
+        // 7|     A   C     E E   |
+        // 8|         C           |
+        // 9|         C           |
+
+        let pepper = rand::random::<[u8; PEPPER_LEN]>().to_vec();
+        let mut state = GameState {
+            pepper: pepper.clone(),
+            ..example_state()
+        };
+
+        // Round 1
+        let expected_state = state.clone();
+        assert_eq!(state.apply_shot((1, 1)), HitType::Miss);
+        assert_eq!(state, expected_state, "round 1 should not change state");
+
+        // Round 2
+        let expected_state = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal).with_hit_mask(0x02),
+                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
+                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+            ],
+            pepper: pepper.clone(),
+        };
+        assert_eq!(state.apply_shot((4, 1)), HitType::Hit);
+        assert_eq!(state, expected_state, "round 2 does not match expected");
+
+        // Round 3
+        // Duplicate hit results in no state change
+        let expected_state = state.clone();
+        assert_eq!(state.apply_shot((4, 1)), HitType::Hit);
+        assert_eq!(state, expected_state, "round 3 does not match expected");
+
+        // Round 4
+        let expected_state = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal).with_hit_mask(0x03),
+                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
+                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+            ],
+            pepper: pepper.clone(),
+        };
+        assert_eq!(state.apply_shot((3, 1)), HitType::Hit);
+        assert_eq!(state, expected_state, "round 4 does not match expected");
+
+        // Round 5
+        let expected_state = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal).with_hit_mask(0x0b),
+                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
+                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+            ],
+            pepper: pepper.clone(),
+        };
+        assert_eq!(state.apply_shot((6, 1)), HitType::Hit);
+        assert_eq!(state, expected_state, "round 5 does not match expected");
+
+        // Round 6
+        let expected_state = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal).with_hit_mask(0x0f),
+                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
+                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+            ],
+            pepper: pepper.clone(),
+        };
+        assert_eq!(
+            state.apply_shot((5, 1)),
+            HitType::Sunk(ShipClass::Battleship)
+        );
+        assert_eq!(state, expected_state, "round 6 does not match expected");
+    }
+
+    #[test]
+    fn game_over_on_final_sink() {
+        let mut state = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+            ],
+            ..GameState::new(rand::random())
+        };
+
+        // Sinking one ship, or hitting the last one, does not end the game.
+        for shot in [(7, 7), (8, 7), (7, 5), (0, 0), (8, 5)] {
+            let round = state.play_round(shot.into()).unwrap();
+            assert!(!round.game_over, "game over after {:?}", shot);
+        }
+        let round = state.play_round(Position { x: 9, y: 5 }).unwrap();
+        assert_eq!(round.hit, HitType::Sunk(ShipClass::Submarine));
+        assert!(round.game_over);
+    }
+
+    #[test]
+    fn diagonal_carrier() {
+        // Board
+        //  | 0 1 2 3 4 5 6 7 8 9 |
+        // 0| A                   |
+        // 1|   A   B B B B       |
+        // 2|     A               |
+        // 3|       A             |
+        // 4|         A           |
+        // 5|               S S S |
+        // 6|                     |
+        // 7|         C     D D   |
+        // 8|         C           |
+        // 9|         C           |
+        let mut state = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Carrier, (0, 0), Direction::DiagonalDown),
+                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
+                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
+                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+            ],
+            ..GameState::new(rand::random())
+        };
+        let diagonal_config = GameConfig {
+            allow_diagonal: true,
+            ..Default::default()
+        };
+        assert!(!state.check());
+        assert!(state.check_with(&diagonal_config));
+
+        for i in 0..4 {
+            assert_eq!(state.apply_shot((i, i)), HitType::Hit);
+        }
+        assert_eq!(state.apply_shot((5, 5)), HitType::Miss);
+        assert_eq!(state.apply_shot((4, 4)), HitType::Sunk(ShipClass::Carrier));
+
+        // An upward diagonal must fit between its anchor and the top of the board.
+        let ship = Ship::new(ShipClass::Carrier, (0, 4), Direction::DiagonalUp);
+        assert!(ship.in_bounds());
+        assert_eq!(ship.points().last(), Some(Position { x: 4, y: 0 }));
+        assert!(!Ship::new(ShipClass::Carrier, (0, 3), Direction::DiagonalUp).in_bounds());
+        assert!(!Ship::new(ShipClass::Carrier, (6, 6), Direction::DiagonalDown).in_bounds());
+    }
+
+    #[test]
+    fn cycle_direction() {
+        let mut dir = Direction::Horizontal;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            seen.push(dir);
+            dir = dir.cycle(true);
+        }
+        assert_eq!(dir, Direction::Horizontal);
+        assert_eq!(
+            seen,
+            vec![
+                Direction::Horizontal,
+                Direction::Vertical,
+                Direction::DiagonalDown,
+                Direction::DiagonalUp
+            ]
+        );
+
+        assert_eq!(Direction::Vertical.cycle(false), Direction::Horizontal);
+        assert_eq!(Direction::DiagonalUp.cycle(false), Direction::Horizontal);
+    }
+
+    #[test]
+    fn fleet_digest() {
+        let standard = Fleet::standard();
+        assert_eq!(
+            standard.digest().to_string(),
+            "35aa032df4e44ad081ee1a47f6179c3efdc1f29e9acc5985b73de34eb69bf340"
+        );
+
+        let reordered = Fleet {
+            classes: ShipClass::list().iter().rev().copied().collect(),
+        };
+        assert!(standard.eq_rules(&reordered));
+        assert_eq!(standard.digest(), reordered.digest());
+
+        let extra_destroyer = Fleet {
+            classes: [ShipClass::list(), &[ShipClass::Destroyer]].concat(),
+        };
+        assert!(!standard.eq_rules(&extra_destroyer));
+        assert_ne!(standard.digest(), extra_destroyer.digest());
+    }
+
+    #[test]
+    fn round_context_mismatch() {
+        let context = MatchContext {
+            players: vec!["alice".to_string(), "bob".to_string()],
+            match_id: "final".to_string(),
+            timestamp: 1_700_000_000,
+        };
+        let other = MatchContext {
+            match_id: "semifinal".to_string(),
+            ..context.clone()
+        };
+        assert_ne!(context.digest(), other.digest());
+
+        let mut state = GameState::new(rand::random());
+        let round_commit = state
+            .play_round(Position { x: 1, y: 1 })
+            .unwrap()
+            .with_context(Some(context.digest()));
+        assert_eq!(round_commit.check_context(Some(&context.digest())), Ok(()));
+        assert_eq!(
+            round_commit.check_context(Some(&other.digest())),
+            Err(GameError::ContextMismatch)
+        );
+        assert_eq!(
+            round_commit.check_context(None),
+            Err(GameError::ContextMismatch)
+        );
+
+        // Blinding keeps the context in the clear, and opening restores it.
+        let nonce = rand::random();
+        let blinded = round_commit.blind(nonce);
+        assert_eq!(blinded.context, Some(context.digest()));
+        assert_eq!(
+            blinded.open(&round_commit.reveal(nonce)),
+            Some(round_commit)
+        );
+    }
+
+    #[test]
+    fn init_fleet_mismatch() {
+        let state = GameState::new(rand::random());
+        let weaker = Fleet {
+            classes: ShipClass::list()[..4].to_vec(),
+        };
+        let init_commit = InitCommit {
+            state: state.commit(),
+            fleet: weaker.digest(),
+            layout: None,
+            context: None,
+        };
+
+        assert_eq!(init_commit.check_fleet(&weaker), Ok(()));
+        assert_eq!(
+            init_commit.check_fleet(&Fleet::standard()),
+            Err(GameError::FleetMismatch {
+                expected: Fleet::standard().digest(),
+                actual: weaker.digest(),
+            })
+        );
+    }
+
+    #[test]
+    fn ship_class_chars() {
+        for class in ShipClass::list() {
+            assert_eq!(ShipClass::from_char(class.to_char()), Some(*class));
+        }
+        assert_eq!(ShipClass::Carrier.to_char(), 'A');
+        assert_eq!(ShipClass::Cruiser.to_char(), 'C');
+        assert_eq!(ShipClass::from_char('x'), None);
+    }
+
+    #[test]
+    fn pack_ship() {
+        let ships = [
+            Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+            Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal).with_hit_mask(0x0f),
+            Ship::new(ShipClass::Cruiser, (4, 7), Direction::DiagonalUp).with_hit_mask(0x02),
+            Ship::new(ShipClass::Submarine, (9, 9), Direction::DiagonalDown),
+            Ship::new(ShipClass::Destroyer, (0, 0), Direction::Horizontal).with_hit_mask(0x01),
+        ];
+        for ship in ships.iter() {
+            let packed = ship.pack().unwrap();
+            assert!(packed < 1 << 18);
+            assert_eq!(Ship::unpack(packed).as_ref(), Some(ship));
+        }
+
+        // Class indices above 4 are invalid.
+        let packed = ships[0].pack().unwrap();
+        assert_eq!(Ship::unpack((packed & !0x7) | 5), None);
+        assert_eq!(Ship::unpack((packed & !0x7) | 7), None);
+        // As are positions off the board, hits past the end of the ship, and unused bits.
+        assert_eq!(Ship::unpack(packed | (0xf << 3)), None);
+        assert_eq!(Ship::unpack(ships[4].pack().unwrap() | (0x04 << 13)), None);
+        assert_eq!(Ship::unpack(packed | (1 << 18)), None);
+
+        // Ships that do not fit the packing are rejected rather than truncated.
+        let off_board = Ship::new(ShipClass::Destroyer, (16, 0), Direction::Horizontal);
+        assert_eq!(off_board.pack(), None);
+        assert_eq!(ships[0].clone().with_hit_mask(0x20).pack(), None);
+        // As are hits past the end of the ship, which would not unpack.
+        assert_eq!(ships[4].clone().with_hit_mask(0b11111).pack(), None);
+    }
+
+    #[test]
+    fn endpoints() {
+        let ship = Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal);
+        let points: Vec<Position> = ship.points().collect();
+        assert_eq!(ship.endpoints(), (points[0], points[3]));
+        assert_eq!(ship.endpoints(), ((3, 1).into(), (6, 1).into()));
+
+        let ship = Ship::new(ShipClass::Cruiser, (2, 5), Direction::DiagonalUp);
+        assert_eq!(ship.endpoints(), ((2, 5).into(), (4, 3).into()));
+    }
+
+    #[test]
+    fn footprint() {
+        let cells = ShipClass::Carrier.footprint((7, 2), Direction::Horizontal);
+        assert_eq!(
+            cells,
+            vec![
+                Position { x: 7, y: 2 },
+                Position { x: 8, y: 2 },
+                Position { x: 9, y: 2 },
+                Position { x: 10, y: 2 },
+                Position { x: 11, y: 2 },
+            ]
+        );
+        assert_eq!(cells.iter().filter(|pos| !pos.in_bounds()).count(), 2);
+
+        // For a valid placement, the footprint matches the points of the placed ship.
+        let ship = Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical);
+        assert_eq!(
+            ShipClass::Cruiser.footprint(ship.pos, ship.dir),
+            ship.points().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn spectator_commit() {
+        let mut state = example_state();
+
+        let mut hits = 0;
+        let mut is_hit = Vec::new();
+        for shot in [(7, 7), (1, 1), (8, 7)] {
+            let round_commit = state.play_round(shot.into()).unwrap();
+            let spectator_commit = SpectatorCommit::from_round(&round_commit, hits);
+            assert_eq!(spectator_commit.old_state, round_commit.old_state);
+            assert_eq!(spectator_commit.new_state, round_commit.new_state);
+            hits = spectator_commit.hits;
+            is_hit.push(spectator_commit.is_hit);
+        }
+        // The sinking shot is reported as a plain hit.
+        assert_eq!(is_hit, vec![true, false, true]);
+        assert_eq!(hits, 2);
+    }
+
+    #[test]
+    fn shot_sequence() {
+        let shots: Vec<Position> = [(1, 1), (7, 7), (8, 7)].map(Position::from).to_vec();
+        assert_eq!(validate_shot_sequence(&shots), Ok(()));
+        assert_eq!(validate_shot_sequence(&[]), Ok(()));
+
+        let repeated: Vec<Position> = [(1, 1), (7, 7), (8, 7), (7, 7), (1, 1)]
+            .map(Position::from)
+            .to_vec();
+        assert_eq!(
+            validate_shot_sequence(&repeated),
+            Err(GameError::DuplicateShot((7, 7).into()))
+        );
+
+        let out_of_bounds: Vec<Position> = [(1, 1), (10, 0)].map(Position::from).to_vec();
+        assert_eq!(
+            validate_shot_sequence(&out_of_bounds),
+            Err(GameError::ShotOutOfBounds((10, 0).into()))
+        );
+    }
+
+    #[test]
+    fn damage_report_commit() {
+        let mut state = example_state();
+        let old_state = state.commit();
+
+        let shots: Vec<Position> = [(7, 7), (1, 1), (8, 7), (2, 3)]
+            .map(Position::from)
+            .to_vec();
+        let rounds: Vec<RoundCommit> = shots
+            .iter()
+            .map(|&shot| state.play_round(shot).unwrap())
+            .collect();
+        let report = DamageReportCommit::from_rounds(old_state, shots, &rounds);
+        assert_eq!((report.hits, report.sinks), (3, 1));
+        assert_eq!(report.new_state, state.commit());
+
+        let empty = DamageReportCommit::from_rounds(old_state, Vec::new(), &[]);
+        assert_eq!((empty.hits, empty.sinks), (0, 0));
+        assert_eq!(empty.new_state, old_state);
+    }
+
+    #[test]
+    fn hidden_fleet_commit() {
+        let mut state = example_state();
+
+        let mut outcomes = Vec::new();
+        for shot in [(7, 7), (1, 1), (8, 7)] {
+            let round_commit = state.play_round(shot.into()).unwrap();
+            let remaining = state.remaining_classes().len() as u8;
+            let hidden_commit = HiddenFleetCommit::from_round(&round_commit, remaining);
+            assert_eq!(hidden_commit.old_state, round_commit.old_state);
+            assert_eq!(hidden_commit.new_state, round_commit.new_state);
+            outcomes.push((hidden_commit.outcome, hidden_commit.ships_remaining));
+        }
+        assert_eq!(
+            outcomes,
+            vec![
+                (HiddenOutcome::Hit, 5),
+                (HiddenOutcome::Miss, 5),
+                (HiddenOutcome::SunkSomething, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn canonicalize() {
+        let pepper = rand::random::<[u8; PEPPER_LEN]>().to_vec();
+        let mut state = GameState {
+            pepper: pepper.clone(),
+            ..example_state()
+        };
+        state.ships[1].hit_mask = 0x02;
+        state.ships[4].hit_mask = 0x01;
+        // The same board, with ships in a different order and hit bits beyond the ship spans.
+        let equivalent_state = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal).with_hit_mask(0xf1),
+                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
+                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal).with_hit_mask(0x08),
+                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal).with_hit_mask(0x82),
+                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+            ],
+            pepper: pepper.clone(),
+        };
+
+        assert_ne!(
+            state.to_canonical_bytes(),
+            equivalent_state.to_canonical_bytes()
+        );
+        assert_eq!(state.canonical(), state);
+        assert_eq!(
+            state.canonical().to_canonical_bytes(),
+            equivalent_state.canonical().to_canonical_bytes()
+        );
+
+        let mut canonicalized = equivalent_state.clone();
+        canonicalized.canonicalize();
+        assert_eq!(canonicalized, equivalent_state.canonical());
+    }
+
+    #[test]
+    fn same_board() {
+        let mut ships = example_ships();
+        ships[1].hit_mask = 0x02;
+        let state = GameState {
+            ships: ships.clone(),
+            pepper: vec![0x01; 16],
+        };
+        let mut other = GameState {
+            ships: ships.into_iter().rev().collect(),
+            pepper: vec![0x02; 16],
+        };
+        assert!(state.same_board(&other));
+        assert_ne!(state, other);
+
+        // A hit on one board but not the other is a different board.
+        other.apply_shot((7, 7));
+        assert!(!state.same_board(&other));
+    }
+
+    #[test]
+    fn layout_digest() {
+        let mut state = example_state();
+        let mut other = state.clone();
+        other.pepper = rand::random::<[u8; PEPPER_LEN]>().to_vec();
+        other.ships.reverse();
+        let layout = state.layout_digest();
+
+        // The layout is unaffected by pepper, ship order, or hits.
+        state.apply_shot((7, 7));
+        assert_eq!(state.layout_digest(), layout);
+        assert_eq!(other.layout_digest(), layout);
+
+        other.ships[0] = Ship::new(ShipClass::Destroyer, (0, 0), Direction::Horizontal);
+        assert_ne!(other.layout_digest(), layout);
+    }
+
+    #[test]
+    fn layout_fingerprint() {
+        let mut state = example_state();
+        let mut other = state.clone();
+        other.pepper = rand::random::<[u8; PEPPER_LEN]>().to_vec();
+        other.ships.reverse();
+        let key = Digest::from(rand::random::<[u32; 8]>());
+        let fingerprint = state.layout_fingerprint(&key);
+
+        // Like the layout digest, the fingerprint is unaffected by pepper, ship order, or hits.
+        state.apply_shot((7, 7));
+        assert_eq!(state.layout_fingerprint(&key), fingerprint);
+        assert_eq!(other.layout_fingerprint(&key), fingerprint);
+
+        // But it differs from the unkeyed digest, and between keys.
+        assert_ne!(fingerprint.fingerprint, state.layout_digest());
+        let other_key = Digest::from(rand::random::<[u32; 8]>());
+        let other_fingerprint = state.layout_fingerprint(&other_key);
+        assert_ne!(other_fingerprint.key, fingerprint.key);
+        assert_ne!(other_fingerprint.fingerprint, fingerprint.fingerprint);
+    }
+
+    #[test]
+    fn verify_transition() {
+        let mut state = example_state();
+
+        let mut commit = state.commit();
+        for shot in [(7, 7), (1, 1), (8, 7)] {
+            let round_commit = state.play_round(shot.into()).unwrap();
+            commit = round_commit
+                .verify_transition(&commit, shot.into())
+                .unwrap();
+        }
+        assert_eq!(commit, state.commit());
+
+        let prev = state.commit();
+        let round_commit = state.play_round((2, 3).into()).unwrap();
+        assert_eq!(
+            round_commit.verify_transition(&round_commit.new_state, (2, 3).into()),
+            Err(GameError::StateMismatch)
+        );
+        assert_eq!(
+            round_commit.verify_transition(&prev, (2, 4).into()),
+            Err(GameError::ShotMismatch((2, 3).into()))
+        );
+
+        let mut tampered = round_commit.clone();
+        tampered.hit = HitType::Miss;
+        assert_eq!(
+            tampered.verify_transition(&prev, (2, 3).into()),
+            Err(GameError::InvalidTransition)
+        );
+    }
+
+    #[test]
+    fn blinded_round() {
+        let mut state = example_state();
+        let round_commit = state.play_round(Position { x: 2, y: 5 }).unwrap();
+        let nonce = rand::random();
+        let blinded = round_commit.blind(nonce);
+
+        // The same round blinded with another nonce cannot be correlated with the first.
+        assert_ne!(blinded.outcome, round_commit.blind(rand::random()).outcome);
+
+        assert_eq!(
+            blinded.open(&round_commit.reveal(nonce)),
+            Some(round_commit.clone())
+        );
+        let mut forged = round_commit.reveal(nonce);
+        forged.hit = HitType::Miss;
+        assert_eq!(blinded.open(&forged), None);
+    }
+
+    #[test]
+    fn shot_reveal() {
+        let reveal = ShotReveal {
+            shot: Position { x: 2, y: 5 },
+            nonce: rand::random(),
+        };
+        let commitment = reveal.digest();
+        assert_eq!(reveal.open(&commitment), Ok(Position { x: 2, y: 5 }));
+
+        // Revealing a different shot, or the same shot with a different nonce, is rejected.
+        let other_shot = ShotReveal {
+            shot: Position { x: 3, y: 5 },
+            ..reveal.clone()
+        };
+        assert_eq!(
+            other_shot.open(&commitment),
+            Err(GameError::ShotRevealMismatch)
+        );
+        let other_nonce = ShotReveal {
+            nonce: rand::random(),
+            ..reveal
+        };
+        assert_eq!(
+            other_nonce.open(&commitment),
+            Err(GameError::ShotRevealMismatch)
+        );
+    }
+
+    #[test]
+    fn round_summary_bytes() {
+        let hits = [
+            HitType::Miss,
+            HitType::Hit,
+            HitType::Sunk(ShipClass::Destroyer),
+            HitType::Invalid,
+            HitType::Deflected,
+        ];
+        for (i, hit) in hits.into_iter().enumerate() {
+            let summary = RoundSummary {
+                old_state: *risc0_zkvm::sha::Impl::hash_bytes(b"old"),
+                new_state: *risc0_zkvm::sha::Impl::hash_bytes(b"new"),
+                shot: CellIndex::try_from(Position { x: 9, y: i as u32 }).unwrap(),
+                hit,
+                game_over: i == 2,
+            };
+            let bytes = summary.to_bytes();
+            assert_eq!(bytes.len(), ROUND_SUMMARY_LEN);
+            assert_eq!(RoundSummary::from_bytes(&bytes), Some(summary));
+        }
+
+        let mut state = GameState {
+            ships: vec![Ship::new(
+                ShipClass::Destroyer,
+                (7, 7),
+                Direction::Horizontal,
+            )],
+            ..GameState::new(rand::random())
+        };
+        state.play_round(Position { x: 7, y: 7 }).unwrap();
+        let round = state.play_round(Position { x: 8, y: 7 }).unwrap();
+        let bytes = round.to_bytes();
+        assert_eq!(bytes[64..], [78, 2, 4, 1]);
+        assert_eq!(RoundSummary::from_bytes(&bytes), Some(round.summary()));
+
+        // Out of range fields and lengths other than the fixed length are rejected.
+        for (offset, value) in [(64, 100), (65, 5), (66, 5), (67, 2)] {
+            let mut invalid = bytes.clone();
+            invalid[offset] = value;
+            assert_eq!(RoundSummary::from_bytes(&invalid), None);
+        }
+        let mut class_on_miss = RoundSummary {
+            hit: HitType::Miss,
+            ..round.summary()
+        }
+        .to_bytes();
+        class_on_miss[66] = 1;
+        assert_eq!(RoundSummary::from_bytes(&class_on_miss), None);
+        assert_eq!(RoundSummary::from_bytes(&bytes[..67]), None);
+        assert_eq!(
+            RoundSummary::from_bytes(&[bytes.clone(), vec![0]].concat()),
+            None
+        );
+    }
+
+    #[test]
+    fn witness_summary() {
+        let input = RoundInput {
+            state: example_state(),
+            shot: Position { x: 2, y: 5 },
+            strict: false,
+            context: None,
+        };
+
+        let summary = input.witness_summary();
+        assert!(summary.contains(&input.state.commit().to_string()));
+        assert!(summary.contains("(2, 5)"));
+        assert!(summary.contains("Hit"));
+    }
+
+    #[test]
+    fn remaining_classes() {
+        let mut state = example_state();
+        assert_eq!(state.remaining_classes(), ShipClass::list());
+
+        // Sink the destroyer and the cruiser, and hit the submarine.
+        for shot in [(7, 7), (8, 7), (4, 7), (4, 8), (4, 9), (7, 5)] {
+            state.apply_shot(shot);
+        }
+        assert_eq!(
+            state.remaining_classes(),
+            vec![
+                ShipClass::Carrier,
+                ShipClass::Battleship,
+                ShipClass::Submarine
+            ]
+        );
+    }
+
+    #[test]
+    fn canonical_class_order() {
+        let mut state = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
+                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
+            ],
+            ..GameState::new(rand::random())
+        };
+        assert_eq!(state.remaining_classes(), ShipClass::list());
+        assert!(state.sunk_ships().is_empty());
+
+        // Sink the destroyer and then the cruiser, which come in reverse order on the board.
+        for shot in [(7, 7), (8, 7), (4, 7), (4, 8), (4, 9)] {
+            state.apply_shot(shot);
+        }
+        assert_eq!(
+            state.remaining_classes(),
+            vec![
+                ShipClass::Carrier,
+                ShipClass::Battleship,
+                ShipClass::Submarine
+            ]
+        );
+        let sunk: Vec<ShipClass> = state.sunk_ships().iter().map(|ship| ship.class).collect();
+        assert_eq!(sunk, vec![ShipClass::Cruiser, ShipClass::Destroyer]);
+    }
+
+    #[test]
+    fn sink_in_order() {
+        let mut state = GameState {
+            ships: vec![Ship::new(
+                ShipClass::Destroyer,
+                (7, 7),
+                Direction::Horizontal,
+            )],
+            ..GameState::new(rand::random())
+        };
+
+        // Hitting the stern before the bow is deflected, and does not damage the ship.
+        let mut out_of_order = state.clone();
+        assert_eq!(
+            out_of_order.apply_shot_with((8, 7), SinkRule::InOrder),
+            HitType::Deflected
+        );
+        assert_eq!(out_of_order, state);
+
+        assert_eq!(
+            state.apply_shot_with((7, 7), SinkRule::InOrder),
+            HitType::Hit
+        );
+        assert_eq!(
+            state.apply_shot_with((8, 7), SinkRule::InOrder),
+            HitType::Sunk(ShipClass::Destroyer)
+        );
+    }
+
+    #[test]
+    fn with_ships() {
+        let ships = example_ships();
+        let pepper = rand::random();
+        let state = GameState::with_ships(ships.clone(), pepper).unwrap();
+        assert_eq!(state.ships, ships);
+        assert_eq!(state.pepper, pepper.to_vec());
+        assert!(state.check());
+
+        // Replace the destroyer with each kind of invalid ship.
+        let with_destroyer = |destroyer: Ship| {
+            let mut ships = ships.clone();
+            ships[4] = destroyer;
+            GameState::with_ships(ships, pepper)
+        };
+        assert_eq!(
+            with_destroyer(Ship::new(
+                ShipClass::Destroyer,
+                (9, 0),
+                Direction::Horizontal
+            )),
+            Err(GameError::OutOfBounds(ShipClass::Destroyer))
+        );
+        assert_eq!(
+            with_destroyer(
+                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal).with_hit_mask(0b100)
+            ),
+            Err(GameError::InvalidHitMask(ShipClass::Destroyer))
+        );
+        assert_eq!(
+            with_destroyer(Ship::new(
+                ShipClass::Destroyer,
+                (2, 7),
+                Direction::Horizontal
+            )),
+            Err(GameError::Overlap(ShipClass::Carrier, ShipClass::Destroyer))
+        );
+        assert_eq!(
+            with_destroyer(Ship::new(
+                ShipClass::Destroyer,
+                (0, 0),
+                Direction::DiagonalDown
+            )),
+            Err(GameError::DiagonalNotAllowed(ShipClass::Destroyer))
+        );
+        assert_eq!(
+            with_destroyer(Ship::new(ShipClass::Cruiser, (0, 0), Direction::Horizontal)),
+            Err(GameError::DuplicateClass(ShipClass::Cruiser))
+        );
+        assert_eq!(
+            GameState::with_ships(ships[..4].to_vec(), pepper),
+            Err(GameError::MissingClass(ShipClass::Destroyer))
+        );
+    }
+
+    #[test]
+    fn damage_taken() {
+        let mut state = example_state();
+        assert_eq!(state.damage_taken(), 0);
+
+        // Misses and repeated hits do not add damage.
+        for shot in [(2, 3), (2, 4), (1, 1), (7, 7), (7, 7)] {
+            state.apply_shot(shot);
+        }
+        assert_eq!(state.damage_taken(), 3);
+    }
+
+    #[test]
+    fn unchanged_round_reuses_commit() {
+        let mut state = example_state();
+
+        // A miss, a hit, a repeated hit, a sinking, and a shot at the sunk ship.
+        for shot in [(1, 1), (7, 7), (7, 7), (8, 7), (8, 7), (1, 1)] {
+            let shot = Position::from(shot);
+            let before = state.clone();
+            let round = state.play_round(shot).unwrap();
+
+            // Recompute every commitment from scratch, as if nothing were reused.
+            let mut naive = before.clone();
+            naive.apply_shot(shot);
+            assert_eq!(naive, state);
+            assert_eq!(round.old_state, before.commit());
+            assert_eq!(round.new_state, naive.commit());
+        }
+    }
+
+    #[test]
+    fn ships_by_damage() {
+        let mut state = example_state();
+        for (ship, hit_mask) in state.ships.iter_mut().zip([0b01011, 0, 0b111, 0b010, 0b01]) {
+            ship.hit_mask = hit_mask;
+        }
+        assert_eq!(
+            state.ships_by_damage(),
+            vec![
+                (ShipClass::Cruiser, 0),
+                (ShipClass::Destroyer, 1),
+                (ShipClass::Carrier, 2),
+                (ShipClass::Submarine, 2),
+                (ShipClass::Battleship, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn peek_shot() {
+        let mut state = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+            ],
+            ..GameState::new(rand::random())
+        };
+
+        for (shot, expected) in [
+            ((0, 0), HitType::Miss),
+            ((7, 7), HitType::Hit),
+            ((8, 7), HitType::Sunk(ShipClass::Destroyer)),
+            ((2, 3), HitType::Hit),
+        ] {
+            let shot = Position::from(shot);
+            let before = state.clone();
+            let peeked = state.peek_shot(shot);
+            assert_eq!(state, before);
+            assert_eq!(peeked, expected);
+            assert_eq!(state.apply_shot(shot), peeked);
+        }
+    }
+
+    #[test]
+    fn large_fleet_overlap() {
+        // A fleet of twenty destroyers, placed side by side in pairs of columns.
+        let fleet = Fleet {
+            classes: vec![ShipClass::Destroyer; 20],
+        };
+        let config = GameConfig {
+            fleet,
+            ..Default::default()
+        };
+        let ships: Vec<Ship> = (0..20)
+            .map(|i| {
+                Ship::new(
+                    ShipClass::Destroyer,
+                    (i % 10, i / 10 * 2),
+                    Direction::Vertical,
+                )
+            })
+            .collect();
+        let mut state = GameState {
+            ships,
+            ..GameState::new(rand::random())
+        };
+        assert!(state.ships.len() >= BITBOARD_MIN_SHIPS);
+        assert!(state.check_with(&config));
+
+        state.ships[19] = Ship::new(ShipClass::Destroyer, (9, 3), Direction::Horizontal);
+        assert!(!state.check_with(&GameConfig {
+            toroidal: true,
+            ..config.clone()
+        }));
+        state.ships[19] = Ship::new(ShipClass::Destroyer, (8, 2), Direction::Horizontal);
+        assert!(!state.check_with(&config));
+
+        // Both overlap checks agree on random sets of ships, with and without overlaps.
+        for _ in 0..1000 {
+            let points: Vec<Vec<Position>> = (0..rand::random_range(0..30))
+                .map(|_| {
+                    let class = ShipClass::list()[rand::random_range(0..NUM_SHIPS)];
+                    let pos = (rand::random_range(0..10), rand::random_range(0..6));
+                    Ship::new(class, pos, Direction::Vertical)
+                        .points()
+                        .collect()
+                })
+                .collect();
+            assert_eq!(overlaps_bitboard(&points), overlaps_pairwise(&points));
+        }
+    }
+
+    #[test]
+    fn hit_positions() {
+        let mut state = example_state();
+        assert_eq!(state.hit_positions(), vec![]);
+
+        for shot in [(2, 6), (1, 1), (2, 3), (2, 6), (0, 9)] {
+            state.apply_shot(shot);
+        }
+        assert_eq!(
+            state.hit_positions(),
+            vec![Position { x: 2, y: 3 }, Position { x: 2, y: 6 }]
+        );
+    }
+
+    #[test]
+    fn bounding_box() {
+        let state = example_state();
+        assert_eq!(state.bounding_box(), Some(((2, 1).into(), (9, 9).into())));
+
+        let corner = GameState {
+            ships: vec![Ship::new(
+                ShipClass::Destroyer,
+                (0, 0),
+                Direction::Horizontal,
+            )],
+            ..GameState::new(rand::random())
+        };
+        assert_eq!(corner.bounding_box(), Some(((0, 0).into(), (1, 0).into())));
+        assert_eq!(GameState::new(rand::random()).bounding_box(), None);
+    }
+
+    #[test]
+    fn occupied_count() {
+        let state = example_state();
+        assert_eq!(state.occupied_count(), 17);
+        assert_eq!(state.empty_count(), 83);
+        assert_eq!(GameState::new(rand::random()).occupied_count(), 0);
+    }
+
+    #[test]
+    fn ship_at() {
+        let state = example_state();
+
+        assert_eq!(
+            state.ship_at(Position { x: 4, y: 9 }),
+            Some((2, &state.ships[2]))
+        );
+        assert_eq!(
+            state.ship_at(Position { x: 6, y: 1 }),
+            Some((1, &state.ships[1]))
+        );
+        assert_eq!(state.ship_at(Position { x: 0, y: 0 }), None);
+        assert_eq!(state.ship_at(Position { x: 10, y: 1 }), None);
+    }
+
+    #[test]
+    fn shared_ocean() {
+        // Board
+        //  | 0 1 2 3 4 5 6 7 8 9 |
+        // 0|                     |
+        // 1|       B B B B       |
+        // 2|                     |
+        // 3|     A               |
+        // 4|     A               |
+        // 5|     A         S S S |
+        // 6|     A               |
+        // 7|     A   C     D D   |
+        // 8|         C           |
+        // 9|         C           |
+        let state_a = example_state();
+
+        // Board
+        //  | 0 1 2 3 4 5 6 7 8 9 |
+        // 0| A A A A A           |
+        // 1|                 B   |
+        // 2|                 B   |
+        // 3|                 B   |
+        // 4|                 B   |
+        // 5|                     |
+        // 6|                     |
+        // 7|           C         |
+        // 8| D D       C         |
+        // 9|           C   S S S |
+        let state_b = GameState {
+            ships: vec![
+                Ship::new(ShipClass::Carrier, (0, 0), Direction::Horizontal),
+                Ship::new(ShipClass::Battleship, (8, 1), Direction::Vertical),
+                Ship::new(ShipClass::Cruiser, (5, 7), Direction::Vertical),
+                Ship::new(ShipClass::Submarine, (7, 9), Direction::Horizontal),
+                Ship::new(ShipClass::Destroyer, (0, 8), Direction::Horizontal),
+            ],
+            ..GameState::new(rand::random())
+        };
+        assert!(state_a.check_shared_ocean(&state_b));
+        assert!(state_b.check_shared_ocean(&state_a));
+
+        // A board always overlaps with itself.
+        assert!(!state_a.check_shared_ocean(&state_a));
+    }
+
+    #[test]
+    fn strict_shot_at_sunk_ship() {
+        let mut state = example_state();
+
+        assert_eq!(state.apply_shot_strict((7, 7)), HitType::Hit);
+        // A repeat shot at a ship that is not yet sunk is allowed.
+        assert_eq!(state.apply_shot_strict((7, 7)), HitType::Hit);
+        assert_eq!(
+            state.apply_shot_strict((8, 7)),
+            HitType::Sunk(ShipClass::Destroyer)
+        );
+
+        let expected_state = state.clone();
+        assert_eq!(state.apply_shot_strict((7, 7)), HitType::Invalid);
+        assert_eq!(state.apply_shot_strict((8, 7)), HitType::Invalid);
+        assert_eq!(
+            state, expected_state,
+            "invalid shot should not change state"
+        );
+        assert_eq!(
+            state.check_shot((7, 7).into(), true),
+            Err(GameError::ShotAtSunkShip((7, 7).into()))
+        );
+        assert_eq!(state.check_shot((7, 7).into(), false), Ok(()));
+        assert_eq!(
+            state.check_shot((7, 10).into(), false),
+            Err(GameError::ShotOutOfBounds((7, 10).into()))
+        );
+
+        // Without the strict mode, the shot is reported as sinking the ship again.
+        assert_eq!(
+            state.apply_shot((7, 7)),
+            HitType::Sunk(ShipClass::Destroyer)
+        );
+        assert_eq!(state.apply_shot_strict((6, 7)), HitType::Miss);
+    }
 
     #[test]
-    fn basic() {
+    fn partial_board() {
         // Board
         //  | 0 1 2 3 4 5 6 7 8 9 |
         // 0|                     |
-        // 1|       BBBB          |
+        // 1|       B B B B       |
         // 2|                     |
         // 3|     A               |
         // 4|     A               |
-        // 5|     A         SSS   |
+        // 5|     A               |
         // 6|     A               |
-        // 7|     A   C     DD    |
+        // 7|     A   C           |
         // 8|         C           |
         // 9|         C           |
-        let state = GameState {
+        let mut state = GameState {
             ships: vec![
                 Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
                 Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
                 Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
-                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
-                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
             ],
-            pepper: rand::random(),
+            ..GameState::new(rand::random())
         };
+        assert_eq!(state.check_partial(), Ok(()));
+        assert!(!state.check());
 
-        assert!(state.check());
+        // An empty board is a valid partial board.
+        assert_eq!(GameState::new(rand::random()).check_partial(), Ok(()));
+
+        state
+            .ships
+            .push(Ship::new(ShipClass::Destroyer, (3, 0), Direction::Vertical));
+        assert_eq!(
+            state.check_partial(),
+            Err(GameError::Overlap(
+                ShipClass::Battleship,
+                ShipClass::Destroyer
+            ))
+        );
+
+        state.ships.pop();
+        state
+            .ships
+            .push(Ship::new(ShipClass::Cruiser, (7, 5), Direction::Horizontal));
+        assert_eq!(
+            state.check_partial(),
+            Err(GameError::DuplicateClass(ShipClass::Cruiser))
+        );
+
+        state.ships.pop();
+        state.ships.push(Ship::new(
+            ShipClass::Submarine,
+            (8, 5),
+            Direction::Horizontal,
+        ));
+        assert_eq!(
+            state.check_partial(),
+            Err(GameError::OutOfBounds(ShipClass::Submarine))
+        );
     }
 
     #[test]
-    fn overlap() {
-        // Board
-        //  | 0 1 2 3 4 5 6 7 8 9 |
-        // 0|                     |
-        // 1|       BBBB          |
-        // 2|     C               |
-        // 3|     *               |
-        // 4|     *               |
-        // 5|     A         SSS   |
-        // 6|     A               |
-        // 7|     A         DD    |
-        // 8|                     |
-        // 9|                     |
+    fn sunk_cells() {
+        let mut state = example_state();
+
+        let hit = state.apply_shot((7, 7));
+        assert_eq!(hit, HitType::Hit);
+        assert_eq!(state.sunk_cells(&hit), None);
+
+        let hit = state.apply_shot((8, 7));
+        assert_eq!(hit, HitType::Sunk(ShipClass::Destroyer));
+        assert_eq!(
+            state.sunk_cells(&hit),
+            Some(vec![Position { x: 7, y: 7 }, Position { x: 8, y: 7 }])
+        );
+
+        assert_eq!(state.sunk_cells(&HitType::Miss), None);
+    }
+
+    #[test]
+    fn ship_cells() {
+        let state = example_state();
+
+        assert_eq!(
+            state.ship_cells(ShipClass::Carrier),
+            Some(vec![
+                Position { x: 2, y: 3 },
+                Position { x: 2, y: 4 },
+                Position { x: 2, y: 5 },
+                Position { x: 2, y: 6 },
+                Position { x: 2, y: 7 },
+            ])
+        );
+        assert_eq!(
+            GameState::new(rand::random()).ship_cells(ShipClass::Carrier),
+            None
+        );
+    }
+
+    #[test]
+    fn commit_is_stable() {
+        // Same board as the basic test, with a fixed pepper.
         let state = GameState {
-            ships: vec![
-                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
-                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
-                Ship::new(ShipClass::Cruiser, (2, 3), Direction::Vertical),
-                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
-                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
-            ],
-            pepper: rand::random(),
+            pepper: vec![0x42; 16],
+            ..example_state()
         };
 
-        assert!(!state.check());
+        // If this test fails, the encoding of the game state has changed and all existing
+        // commitments are invalidated.
+        assert_eq!(
+            state.board_digest().to_string(),
+            "3f887425d271624f1084b7e44b07a5ecb69c8eaf15d91f7f3e8f77234ef1d11f"
+        );
+        assert_eq!(
+            state.commit().to_string(),
+            "cfd4438420386e37f1ab534068d9511f9d3a10ccc800a3b81dbe56f01029d6d3"
+        );
     }
 
     #[test]
-    fn rounds() {
-        // Board
-        //  | 0 1 2 3 4 5 6 7 8 9 |
-        // 0|                     |
-        // 1|       B B B B       |
-        // 2|                     |
-        // 3|     A               |
-        // 4|     A               |
-        // 5|     A         D D D |
-        // 6|     A               |
-        // 7|     A   C     E E   |
-        // 8|         C           |
-        // 9|         C           |
+    fn settlement_digest_round_trip() {
+        let state = example_state();
 
-        let pepper = rand::random();
+        let words = state.settlement_digest();
+        assert_eq!(words.as_slice(), state.commit().as_words());
+        assert_eq!(settlement_to_digest(words), state.commit());
+    }
+
+    #[test]
+    fn neighbors() {
+        let neighbors: Vec<_> = Position { x: 4, y: 4 }.neighbors().collect();
+        assert_eq!(
+            neighbors,
+            [(3, 4), (5, 4), (4, 3), (4, 5)].map(Position::from)
+        );
+        let corner: Vec<_> = Position { x: 0, y: 9 }.neighbors().collect();
+        assert_eq!(corner, [(1, 9), (0, 8)].map(Position::from));
+    }
+
+    #[test]
+    fn adjacency_hint() {
         let mut state = GameState {
             ships: vec![
                 Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
-                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
-                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
-                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
                 Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
             ],
-            pepper,
+            ..GameState::new(rand::random())
         };
+        assert!(state.adjacent_to_ship(Position { x: 1, y: 5 }));
+        assert!(state.adjacent_to_ship(Position { x: 2, y: 2 }));
+        assert!(!state.adjacent_to_ship(Position { x: 1, y: 2 }));
 
-        // Round 1
-        let expected_state = state.clone();
-        assert_eq!(state.apply_shot((1, 1)), HitType::Miss);
-        assert_eq!(state, expected_state, "round 1 should not change state");
+        let round = state.play_round(Position { x: 3, y: 4 }).unwrap();
+        assert!(AdjacencyHintCommit::from_round(&round, &state).adjacent_to_ship);
+        let round = state.play_round(Position { x: 0, y: 0 }).unwrap();
+        assert!(!AdjacencyHintCommit::from_round(&round, &state).adjacent_to_ship);
 
-        // Round 2
-        let expected_state = GameState {
-            ships: vec![
-                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
-                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal).with_hit_mask(0x02),
-                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
-                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
-                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
-            ],
-            pepper,
-        };
-        assert_eq!(state.apply_shot((4, 1)), HitType::Hit);
-        assert_eq!(state, expected_state, "round 2 does not match expected");
+        // Hits are never given a hint.
+        let round = state.play_round(Position { x: 2, y: 4 }).unwrap();
+        assert_eq!(round.hit, HitType::Hit);
+        assert!(!AdjacencyHintCommit::from_round(&round, &state).adjacent_to_ship);
+    }
 
-        // Round 3
-        // Duplicate hit results in no state change
-        let expected_state = state.clone();
-        assert_eq!(state.apply_shot((4, 1)), HitType::Hit);
-        assert_eq!(state, expected_state, "round 3 does not match expected");
+    #[test]
+    fn position_range() {
+        let positions: Vec<Position> = Position::range((2, 3), (4, 5)).collect();
+        assert_eq!(
+            positions,
+            [
+                (2, 3),
+                (3, 3),
+                (4, 3),
+                (2, 4),
+                (3, 4),
+                (4, 4),
+                (2, 5),
+                (3, 5),
+                (4, 5)
+            ]
+            .map(Position::from)
+        );
 
-        // Round 4
-        let expected_state = GameState {
-            ships: vec![
-                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
-                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal).with_hit_mask(0x03),
-                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
-                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
-                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
-            ],
-            pepper,
-        };
-        assert_eq!(state.apply_shot((3, 1)), HitType::Hit);
-        assert_eq!(state, expected_state, "round 4 does not match expected");
+        // The range is clipped to the board.
+        assert_eq!(Position::range((8, 8), (20, 20)).count(), 4);
+        assert_eq!(Position::range((10, 0), (20, 20)).count(), 0);
+        assert_eq!(Position::all().count(), BOARD_SIZE * BOARD_SIZE);
+    }
 
-        // Round 5
-        let expected_state = GameState {
-            ships: vec![
-                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
-                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal).with_hit_mask(0x0b),
-                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
-                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
-                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
-            ],
-            pepper,
+    #[test]
+    fn position_index_round_trip() {
+        let mut expected_index = 0;
+        for y in 0..BOARD_SIZE as u32 {
+            for x in 0..BOARD_SIZE as u32 {
+                let pos = Position { x, y };
+                assert_eq!(pos.to_index(), Some(expected_index));
+                assert_eq!(Position::from_index(expected_index), Some(pos));
+                expected_index += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn position_index_out_of_bounds() {
+        assert_eq!(Position::from_index(100), None);
+        assert_eq!(Position::from_index(u8::MAX), None);
+        assert_eq!(Position { x: 10, y: 0 }.to_index(), None);
+        assert_eq!(Position { x: 0, y: u32::MAX }.to_index(), None);
+    }
+
+    #[test]
+    fn position_try_new() {
+        assert_eq!(Position::try_new(9, 9), Ok(Position { x: 9, y: 9 }));
+        assert_eq!(
+            Position::try_new(10, 0),
+            Err(GameError::ShotOutOfBounds(Position { x: 10, y: 0 }))
+        );
+        assert_eq!(
+            Position::try_new(0, u32::MAX),
+            Err(GameError::ShotOutOfBounds(Position { x: 0, y: u32::MAX }))
+        );
+    }
+
+    #[test]
+    fn cell_index_round_trip() {
+        for pos in Position::all() {
+            let cell = CellIndex::try_from(pos).unwrap();
+            assert_eq!(Some(cell.index()), pos.to_index());
+            assert_eq!(Position::from(cell), pos);
+            assert_eq!(cell.to_string(), pos.to_string());
+
+            let words = risc0_zkvm::serde::to_vec(&cell).unwrap();
+            assert_eq!(
+                risc0_zkvm::serde::from_slice::<CellIndex, _>(&words),
+                Ok(cell)
+            );
+            let bytes = canonical_bincode().serialize(&cell).unwrap();
+            assert_eq!(bytes, [cell.index()]);
+            assert_eq!(
+                canonical_bincode()
+                    .deserialize::<CellIndex>(&bytes)
+                    .unwrap(),
+                cell
+            );
+        }
+    }
+
+    #[test]
+    fn cell_index_out_of_range() {
+        assert_eq!(
+            CellIndex::try_from(100),
+            Err(GameError::InvalidCellIndex(100))
+        );
+        assert_eq!(
+            CellIndex::try_from(Position { x: 10, y: 0 }),
+            Err(GameError::ShotOutOfBounds(Position { x: 10, y: 0 }))
+        );
+
+        // Indices off the board are also rejected when decoding.
+        assert!(canonical_bincode()
+            .deserialize::<CellIndex>(&[100])
+            .is_err());
+        assert!(canonical_bincode()
+            .deserialize::<CellIndex>(&[u8::MAX])
+            .is_err());
+        let words = risc0_zkvm::serde::to_vec(&100u8).unwrap();
+        assert!(risc0_zkvm::serde::from_slice::<CellIndex, _>(&words).is_err());
+    }
+
+    #[test]
+    fn rotate_ship() {
+        let ship = Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical).with_hit_mask(0x02);
+
+        let rotated = ship.clone().rotated();
+        assert_eq!(rotated.dir, Direction::Horizontal);
+        assert_eq!(rotated.pos, ship.pos);
+        assert_eq!(rotated.hit_mask, ship.hit_mask);
+        assert_eq!(rotated.rotated(), ship);
+    }
+
+    #[test]
+    fn transform_board() {
+        let mut state = example_state();
+        state.ships[0].hit_mask = 0b00011;
+        state.ships[4].hit_mask = 0b01;
+
+        // Rotating four times returns the original layout, including the hits.
+        let rotated = state.rotate_90();
+        assert!(rotated.check());
+        assert_ne!(rotated, state);
+        assert_eq!(rotated.rotate_90().rotate_90().rotate_90(), state);
+
+        for mirrored in [state.mirror_horizontal(), state.mirror_vertical()] {
+            assert!(mirrored.check());
+            assert_eq!(mirrored.ships.len(), state.ships.len());
+            assert_eq!(mirrored.damage_taken(), state.damage_taken());
+        }
+        assert_eq!(state.mirror_horizontal().mirror_horizontal(), state);
+        assert_eq!(state.mirror_vertical().mirror_vertical(), state);
+
+        // Mirroring the destroyer left to right puts its bow, which was hit, at the other end.
+        assert_eq!(
+            state.mirror_horizontal().ships[4],
+            Ship::new(ShipClass::Destroyer, (1, 7), Direction::Horizontal).with_hit_mask(0b10)
+        );
+        // A quarter turn clockwise takes the horizontal destroyer to a vertical one.
+        assert_eq!(
+            rotated.ships[4],
+            Ship::new(ShipClass::Destroyer, (2, 7), Direction::Vertical).with_hit_mask(0b01)
+        );
+        // The hit cells are the same cells of the board, mapped through the rotation.
+        let hits = |state: &GameState| -> Vec<Position> {
+            let mut hits: Vec<Position> = Position::all()
+                .filter(|pos| {
+                    state.ship_at(*pos).is_some_and(|(_, ship)| {
+                        let offset = ship.points().position(|p| p == *pos).unwrap();
+                        ship.hit_mask & (1 << offset) != 0
+                    })
+                })
+                .collect();
+            hits.sort_by_key(|pos| (pos.y, pos.x));
+            hits
         };
-        assert_eq!(state.apply_shot((6, 1)), HitType::Hit);
-        assert_eq!(state, expected_state, "round 5 does not match expected");
+        let mut expected: Vec<Position> = hits(&state)
+            .into_iter()
+            .map(|pos| Position {
+                x: 9 - pos.y,
+                y: pos.x,
+            })
+            .collect();
+        expected.sort_by_key(|pos| (pos.y, pos.x));
+        assert_eq!(hits(&rotated), expected);
+    }
 
-        // Round 6
-        let expected_state = GameState {
-            ships: vec![
-                Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
-                Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal).with_hit_mask(0x0f),
-                Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
-                Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
-                Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
-            ],
-            pepper,
+    #[test]
+    fn toroidal_board() {
+        let bottom = Position { x: 3, y: 9 };
+        assert_eq!(
+            bottom.step_wrapping(Direction::Vertical, 1, BOARD_SIZE as u32),
+            Position { x: 3, y: 0 }
+        );
+        assert_eq!(
+            Position { x: 0, y: 0 }.step_wrapping(Direction::DiagonalUp, 2, BOARD_SIZE as u32),
+            Position { x: 2, y: 8 }
+        );
+        assert_eq!(
+            Position { x: 12, y: 4 }.clamp(BOARD_SIZE as u32),
+            Position { x: 9, y: 4 }
+        );
+        assert_eq!(Position { x: 12, y: 4 }.clamp(0), Position { x: 0, y: 0 });
+
+        // A destroyer running off the bottom of the board is only valid on a toroidal board.
+        let mut state = example_state();
+        state.ships[4] = Ship::new(ShipClass::Destroyer, (9, 9), Direction::Vertical);
+        let toroidal = GameConfig {
+            toroidal: true,
+            ..Default::default()
         };
+        assert!(!state.check());
+        assert!(state.check_with(&toroidal));
         assert_eq!(
-            state.apply_shot((5, 1)),
-            HitType::Sunk(ShipClass::Battleship)
+            state.ships[4].points_with(&toroidal),
+            vec![Position { x: 9, y: 9 }, Position { x: 9, y: 0 }]
         );
-        assert_eq!(state, expected_state, "round 6 does not match expected");
+    }
+
+    #[test]
+    fn translate_ship() {
+        let ship =
+            Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal).with_hit_mask(0x01);
+
+        assert_eq!(
+            ship.clone().translated(-7, 2),
+            Some(
+                Ship::new(ShipClass::Destroyer, (0, 9), Direction::Horizontal).with_hit_mask(0x01)
+            )
+        );
+
+        // Translating any part of the ship off the board results in None.
+        assert_eq!(ship.clone().translated(-8, 0), None);
+        assert_eq!(ship.clone().translated(2, 0), None);
+        assert_eq!(ship.clone().translated(0, 3), None);
+        assert_eq!(ship.translated(0, -8), None);
     }
 
     #[test]
@@ -497,4 +3510,126 @@ mod tests {
             assert!(state.check());
         }
     }
+
+    #[test]
+    fn pepper_length() {
+        let ships = example_ships();
+        let state = GameState {
+            ships: ships.clone(),
+            ..GameState::new(rand::random())
+        };
+        assert_eq!(state.pepper.len(), PEPPER_LEN);
+        assert!(state.check());
+
+        // A 16-byte pepper is still accepted, but anything shorter is not.
+        let legacy = GameState {
+            ships: ships.clone(),
+            ..GameState::new_16(rand::random())
+        };
+        assert!(legacy.check());
+
+        let short = GameState {
+            ships,
+            pepper: vec![0x42; MIN_PEPPER_LEN - 1],
+        };
+        assert!(!short.check());
+        assert_eq!(
+            short.check_partial(),
+            Err(GameError::PepperTooShort(MIN_PEPPER_LEN - 1))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn sample_fills_pepper() {
+        let state: GameState = rand::random();
+        assert_eq!(state.pepper.len(), PEPPER_LEN);
+        // The chance of the last bytes all being zero by chance is negligible.
+        assert_ne!(&state.pepper[16..], &[0; PEPPER_LEN - 16]);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn sample_exhausts_attempts() {
+        // Twenty-one carriers need more cells than there are on the board.
+        let fleet = Fleet {
+            classes: vec![ShipClass::Carrier; 21],
+        };
+        assert_eq!(
+            GameState::sample_with(&mut rand::rng(), &fleet, 10),
+            Err(GenError::AttemptsExhausted(10))
+        );
+
+        let state = GameState::sample_with(&mut rand::rng(), &Fleet::standard(), 10).unwrap();
+        assert!(state.check());
+    }
+}
+
+#[cfg(all(test, feature = "rand"))]
+mod proptests {
+    use proptest::prelude::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    /// Generates valid game states by seeding the sampler.
+    fn arb_game_state() -> impl Strategy<Value = GameState> {
+        any::<u64>().prop_map(|seed| StdRng::seed_from_u64(seed).random())
+    }
+
+    fn arb_position() -> impl Strategy<Value = Position> {
+        (0..BOARD_SIZE as u32, 0..BOARD_SIZE as u32).prop_map(Position::from)
+    }
+
+    proptest! {
+        #[test]
+        fn sampled_state_is_valid(state in arb_game_state()) {
+            prop_assert!(state.check());
+        }
+
+        #[test]
+        fn shot_at_occupied_cell_hits(
+            mut state in arb_game_state(),
+            ship_index in 0..NUM_SHIPS,
+            offset in 0..5u32,
+        ) {
+            let ship = &state.ships[ship_index];
+            let shot = ship.pos.step(ship.dir, offset % ship.class.span());
+            let hit = state.apply_shot(shot);
+            prop_assert!(
+                matches!(hit, HitType::Hit | HitType::Sunk(_)),
+                "shot at {} was {:?}",
+                shot,
+                hit
+            );
+        }
+
+        #[test]
+        fn shot_at_empty_cell_misses(mut state in arb_game_state(), shot in arb_position()) {
+            prop_assume!(!state.ships.iter().any(|ship| ship.points().any(|p| p == shot)));
+            let expected_state = state.clone();
+            prop_assert_eq!(state.apply_shot(shot), HitType::Miss);
+            prop_assert_eq!(state, expected_state);
+        }
+
+        #[test]
+        fn fog_of_real_game_is_consistent(
+            mut state in arb_game_state(),
+            shots in proptest::collection::vec(arb_position(), 0..60),
+        ) {
+            let shots: Vec<_> = shots
+                .into_iter()
+                .map(|shot| (shot, state.apply_shot(shot)))
+                .collect();
+            prop_assert!(FogBoard::from_shots(&shots).is_consistent(&Fleet::standard()));
+        }
+
+        #[test]
+        fn commit_is_stable_under_reserialization(state in arb_game_state()) {
+            let bytes = state.to_canonical_bytes();
+            let decoded: GameState = canonical_bincode().deserialize(&bytes).unwrap();
+            prop_assert_eq!(decoded.to_canonical_bytes(), bytes);
+            prop_assert_eq!(decoded.commit(), state.commit());
+        }
+    }
 }
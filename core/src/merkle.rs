@@ -0,0 +1,303 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::sha::{Digest, Sha256};
+use serde::{Deserialize, Serialize};
+
+use crate::{CellIndex, GameState, HitType, Position, BOARD_SIZE};
+
+/// Number of cells on the board.
+const NUM_CELLS: usize = BOARD_SIZE * BOARD_SIZE;
+
+/// Number of levels in the tree of cell commitments, whose leaves are the cells padded to a power
+/// of two.
+const CELL_TREE_DEPTH: usize = NUM_CELLS.next_power_of_two().trailing_zeros() as usize;
+
+/// What a cell of the board holds, as committed in its leaf of the [GameState::cell_root]. The
+/// class of the ship is left out, so that opening the cell under a shot reveals no more than the
+/// result of the shot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CellState {
+    Empty,
+    Ship,
+    Hit,
+}
+
+/// Opening of the cell under a shot in the commitments to the board before and after the shot,
+/// which lets a verifier holding only the state commitments check that the board changed in the
+/// way the result of the shot claims.
+///
+/// Each leaf is blinded by a salt derived from the pepper, and the index of the leaf is the shot,
+/// which is public, so the opening reveals nothing about the other cells or which ship was hit.
+/// It checks whether the shot hit a ship, but not the class of a ship claimed to be sunk.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ShotOpening {
+    /// The [GameState::board_digest] before the shot.
+    #[cfg_attr(feature = "schema", schemars(with = "[u32; 8]"))]
+    pub old_board: Digest,
+    /// The [GameState::board_digest] after the shot.
+    #[cfg_attr(feature = "schema", schemars(with = "[u32; 8]"))]
+    pub new_board: Digest,
+    /// The salt of the cell under the shot.
+    #[cfg_attr(feature = "schema", schemars(with = "[u32; 8]"))]
+    pub salt: Digest,
+    /// The cell under the shot before it was applied.
+    pub old_cell: CellState,
+    /// Sibling hashes on the path from the leaf of the cell to the root, which are the same before
+    /// and after the shot, since the shot only changes its own cell.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<[u32; 8]>"))]
+    pub siblings: Vec<Digest>,
+}
+
+impl ShotOpening {
+    /// Checks that the opening is consistent with the claimed result of the shot, and with the
+    /// commitments to the states before and after it. A miss must open an empty cell, and a hit
+    /// must open a cell of a ship that is hit after the shot.
+    pub fn check(
+        &self,
+        shot: CellIndex,
+        hit: &HitType,
+        old_state: &Digest,
+        new_state: &Digest,
+    ) -> bool {
+        let new_cell = match (hit, self.old_cell) {
+            (HitType::Miss, CellState::Empty) => CellState::Empty,
+            (HitType::Hit | HitType::Sunk(_), CellState::Ship | CellState::Hit) => CellState::Hit,
+            // Neither a shot at a sunk ship nor a deflected shot changes the cell.
+            (HitType::Invalid, CellState::Hit) => CellState::Hit,
+            (HitType::Deflected, CellState::Ship) => CellState::Ship,
+            _ => return false,
+        };
+        self.siblings.len() == CELL_TREE_DEPTH
+            && state_commit(&self.old_board, &self.root(shot, self.old_cell)) == *old_state
+            && state_commit(&self.new_board, &self.root(shot, new_cell)) == *new_state
+    }
+
+    /// Root of the cell commitments with the cell under the shot in the given state.
+    pub(crate) fn root(&self, shot: CellIndex, cell: CellState) -> Digest {
+        let mut node = cell_leaf(&self.salt, cell);
+        for (level, sibling) in self.siblings.iter().enumerate() {
+            node = match (shot.index() >> level) & 1 {
+                0 => *risc0_zkvm::sha::Impl::hash_pair(&node, sibling),
+                _ => *risc0_zkvm::sha::Impl::hash_pair(sibling, &node),
+            };
+        }
+        node
+    }
+}
+
+/// Combines the digest of the board with the root of its cell commitments, as in
+/// [GameState::commit].
+pub(crate) fn state_commit(board: &Digest, cell_root: &Digest) -> Digest {
+    *risc0_zkvm::sha::Impl::hash_pair(board, cell_root)
+}
+
+fn cell_leaf(salt: &Digest, cell: CellState) -> Digest {
+    *risc0_zkvm::sha::Impl::hash_bytes(&[salt.as_bytes(), &[cell as u8]].concat())
+}
+
+/// Returns the root of the tree over the given leaves, and the sibling hashes on the path from the
+/// leaf at the given index to the root.
+fn cell_tree(mut level: Vec<Digest>, mut index: usize) -> (Digest, Vec<Digest>) {
+    let mut siblings = Vec::with_capacity(CELL_TREE_DEPTH);
+    while level.len() > 1 {
+        siblings.push(level[index ^ 1]);
+        level = level
+            .chunks(2)
+            .map(|pair| *risc0_zkvm::sha::Impl::hash_pair(&pair[0], &pair[1]))
+            .collect();
+        index /= 2;
+    }
+    (level[0], siblings)
+}
+
+impl GameState {
+    /// Digest of the serialized board, which is hidden by the pepper. The [GameState::commit] binds
+    /// it together with the [GameState::cell_root].
+    pub fn board_digest(&self) -> Digest {
+        *risc0_zkvm::sha::Impl::hash_bytes(&self.to_canonical_bytes())
+    }
+
+    /// Root of the Merkle tree with one salted leaf per cell of the board, in the order of
+    /// [CellIndex], padded with zero digests to a power of two.
+    pub fn cell_root(&self) -> Digest {
+        cell_tree(self.cell_leaves(), 0).0
+    }
+
+    /// Opens the cell under the shot in the commitment to this state, returning the opening and
+    /// the commitment. The opening is for a shot that leaves the board unchanged, and
+    /// [GameState::play_round] updates it when the shot changes the board.
+    pub(crate) fn open_cell(&self, shot: CellIndex) -> (ShotOpening, Digest) {
+        let index = shot.index() as usize;
+        let board = self.board_digest();
+        let (root, siblings) = cell_tree(self.cell_leaves(), index);
+        let opening = ShotOpening {
+            old_board: board,
+            new_board: board,
+            salt: self.cell_salt(index),
+            old_cell: self.cell_states()[index],
+            siblings,
+        };
+        (opening, state_commit(&board, &root))
+    }
+
+    pub(crate) fn cell_state(&self, pos: Position) -> CellState {
+        match self.ship_at(pos) {
+            Some((_, ship)) => {
+                let offset = ship.points().position(|p| p == pos).unwrap_or_default();
+                match (ship.hit_mask >> offset) & 1 {
+                    0 => CellState::Ship,
+                    _ => CellState::Hit,
+                }
+            }
+            None => CellState::Empty,
+        }
+    }
+
+    /// State of each cell, indexed by [CellIndex]. Where ships overlap, which no valid board
+    /// allows, the first ship takes the cell, as for [GameState::ship_at].
+    fn cell_states(&self) -> [CellState; NUM_CELLS] {
+        let mut cells = [CellState::Empty; NUM_CELLS];
+        for ship in self.ships.iter().rev() {
+            for (offset, pos) in ship.points().enumerate() {
+                let Some(index) = pos.to_index() else {
+                    continue;
+                };
+                cells[index as usize] = match (ship.hit_mask >> offset) & 1 {
+                    0 => CellState::Ship,
+                    _ => CellState::Hit,
+                };
+            }
+        }
+        cells
+    }
+
+    /// Salt hiding the state of the cell in its leaf, derived from the pepper so that revealing it
+    /// reveals nothing about the pepper or the other cells.
+    fn cell_salt(&self, index: usize) -> Digest {
+        let bytes = [self.pepper.as_slice(), b"cell", &[index as u8]].concat();
+        *risc0_zkvm::sha::Impl::hash_bytes(&bytes)
+    }
+
+    fn cell_leaves(&self) -> Vec<Digest> {
+        let mut leaves: Vec<Digest> = self
+            .cell_states()
+            .into_iter()
+            .enumerate()
+            .map(|(index, cell)| cell_leaf(&self.cell_salt(index), cell))
+            .collect();
+        leaves.resize(NUM_CELLS.next_power_of_two(), Digest::ZERO);
+        leaves
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::example_state, RoundCommit};
+
+    use super::*;
+
+    fn check(round_commit: &RoundCommit) -> bool {
+        round_commit.opening.check(
+            round_commit.shot,
+            &round_commit.hit,
+            &round_commit.old_state,
+            &round_commit.new_state,
+        )
+    }
+
+    #[test]
+    fn commit_binds_cells() {
+        let state = example_state();
+        assert_eq!(
+            state.commit(),
+            state_commit(&state.board_digest(), &state.cell_root())
+        );
+    }
+
+    #[test]
+    fn hit_opening() {
+        let mut state = example_state();
+        let old_state = state.commit();
+        let round_commit = state.play_round(Position { x: 4, y: 8 }).unwrap();
+        assert_eq!(round_commit.hit, HitType::Hit);
+        assert_eq!(round_commit.opening.old_cell, CellState::Ship);
+        assert_eq!(round_commit.old_state, old_state);
+        assert_eq!(round_commit.new_state, state.commit());
+        assert!(check(&round_commit));
+
+        // Claiming the hit was a miss is rejected, with or without opening the cell as empty.
+        let mut forged = round_commit.clone();
+        forged.hit = HitType::Miss;
+        assert!(!check(&forged));
+        forged.opening.old_cell = CellState::Empty;
+        assert!(!check(&forged));
+
+        // As is an opening with a forged salt, or one checked against the states of another round.
+        let mut forged = round_commit.clone();
+        forged.opening.salt = forged.opening.old_board;
+        assert!(!check(&forged));
+        let next = state.play_round(Position { x: 4, y: 7 }).unwrap();
+        assert!(check(&next));
+        let mut forged = next.clone();
+        forged.opening = round_commit.opening.clone();
+        assert!(!check(&forged));
+    }
+
+    #[test]
+    fn miss_opening() {
+        let mut state = example_state();
+        let round_commit = state.play_round(Position { x: 0, y: 0 }).unwrap();
+        assert_eq!(round_commit.hit, HitType::Miss);
+        assert!(check(&round_commit));
+
+        // Claiming a miss was a hit is rejected, with or without opening the cell as a ship.
+        let mut forged = round_commit.clone();
+        forged.hit = HitType::Hit;
+        assert!(!check(&forged));
+        forged.opening.old_cell = CellState::Ship;
+        assert!(!check(&forged));
+    }
+
+    #[test]
+    fn sunk_and_repeated_openings() {
+        let mut state = example_state();
+        state.play_round(Position { x: 7, y: 7 }).unwrap();
+        let sunk = state.play_round(Position { x: 8, y: 7 }).unwrap();
+        assert!(matches!(sunk.hit, HitType::Sunk(_)));
+        assert!(check(&sunk));
+
+        // A repeated shot opens the cell as already hit, and leaves the board unchanged.
+        let repeated = state.play_round(Position { x: 8, y: 7 }).unwrap();
+        assert_eq!(repeated.opening.old_cell, CellState::Hit);
+        assert_eq!(repeated.old_state, repeated.new_state);
+        assert!(check(&repeated));
+    }
+
+    #[test]
+    fn opening_hides_ship() {
+        // Hits on different ships open leaves of the same shape, with nothing identifying the ship.
+        let mut state = example_state();
+        let first = state.play_round(Position { x: 4, y: 8 }).unwrap();
+        let second = state.play_round(Position { x: 7, y: 7 }).unwrap();
+        assert_eq!(first.opening.old_cell, second.opening.old_cell);
+        assert_ne!(first.opening.salt, second.opening.salt);
+
+        // The salts are not reused across boards with different peppers.
+        let (other, _) = example_state().open_cell(first.shot);
+        assert_ne!(other.salt, first.opening.salt);
+    }
+}
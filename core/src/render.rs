@@ -0,0 +1,148 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::{FogBoard, GameState, Knowledge, Position, BOARD_SIZE};
+
+/// Renders the board in the same form as the diagrams in this crate, with each ship drawn using
+/// [crate::ShipClass::to_char] and cells that have been hit drawn in lowercase.
+impl Display for GameState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, " |")?;
+        for x in 0..BOARD_SIZE {
+            write!(f, " {x}")?;
+        }
+        writeln!(f, " |")?;
+        for y in 0..BOARD_SIZE as u32 {
+            write!(f, "{y}|")?;
+            for x in 0..BOARD_SIZE as u32 {
+                let pos = Position { x, y };
+                let c = match self.ship_at(pos) {
+                    Some((_, ship)) => {
+                        let c = ship.class.to_char();
+                        let offset = ship.points().position(|p| p == pos).unwrap();
+                        match ship.hit_mask & (1 << offset) != 0 {
+                            true => c.to_ascii_lowercase(),
+                            false => c,
+                        }
+                    }
+                    None => ' ',
+                };
+                write!(f, " {c}")?;
+            }
+            writeln!(f, " |")?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders what has been observed of the opponent's board, in the same layout as a [GameState],
+/// with hits drawn as `X` and misses as `o`. The cell of the shot that sunk a ship is drawn using
+/// [crate::ShipClass::to_char], highlighting the sinking.
+impl Display for FogBoard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, " |")?;
+        for x in 0..BOARD_SIZE {
+            write!(f, " {x}")?;
+        }
+        writeln!(f, " |")?;
+        for (y, row) in self.cells.iter().enumerate() {
+            write!(f, "{y}|")?;
+            for knowledge in row.iter() {
+                let c = match knowledge {
+                    Knowledge::Unknown => ' ',
+                    Knowledge::Miss => 'o',
+                    Knowledge::Hit => 'X',
+                    Knowledge::Sunk(class) => class.to_char(),
+                };
+                write!(f, " {c}")?;
+            }
+            writeln!(f, " |")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::example_state, HitType};
+
+    use super::*;
+
+    #[test]
+    fn render_board() {
+        let mut state = example_state();
+        state.apply_shot((2, 5));
+        state.apply_shot((8, 7));
+
+        let expected = [
+            " | 0 1 2 3 4 5 6 7 8 9 |",
+            "0|                     |",
+            "1|       B B B B       |",
+            "2|                     |",
+            "3|     A               |",
+            "4|     A               |",
+            "5|     a         S S S |",
+            "6|     A               |",
+            "7|     A   C     D d   |",
+            "8|         C           |",
+            "9|         C           |",
+        ];
+        assert_eq!(state.to_string().lines().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn render_fog() {
+        let mut state = example_state();
+
+        // Shots from the example game.
+        let shots: Vec<(Position, HitType)> = [
+            (1, 1),
+            (2, 5),
+            (3, 5),
+            (2, 6),
+            (2, 7),
+            (2, 8),
+            (2, 4),
+            (2, 3),
+            (4, 9),
+            (4, 8),
+            (4, 7),
+            (7, 2),
+            (7, 7),
+            (6, 7),
+            (8, 7),
+        ]
+        .into_iter()
+        .map(|shot| (shot.into(), state.apply_shot(shot)))
+        .collect();
+        let fog = FogBoard::from_shots(&shots);
+
+        let expected = [
+            " | 0 1 2 3 4 5 6 7 8 9 |",
+            "0|                     |",
+            "1|   o                 |",
+            "2|               o     |",
+            "3|     A               |",
+            "4|     X               |",
+            "5|     X o             |",
+            "6|     X               |",
+            "7|     X   C   o X D   |",
+            "8|     o   X           |",
+            "9|         X           |",
+        ];
+        assert_eq!(fog.to_string().lines().collect::<Vec<_>>(), expected);
+    }
+}
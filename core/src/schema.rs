@@ -0,0 +1,56 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use schemars::gen::SchemaGenerator;
+
+use crate::{Direction, GameState, HitType, Position, RoundCommit, Ship, ShipClass};
+
+/// Emits a JSON schema describing the JSON encoding of the public types, such that clients can
+/// validate payloads before sending them to the host. Each type is listed under `definitions`.
+pub fn emit_schema() -> String {
+    let mut generator = SchemaGenerator::default();
+    generator.subschema_for::<GameState>();
+    generator.subschema_for::<Ship>();
+    generator.subschema_for::<Position>();
+    generator.subschema_for::<Direction>();
+    generator.subschema_for::<ShipClass>();
+    generator.subschema_for::<HitType>();
+    generator.subschema_for::<RoundCommit>();
+    let schema = generator.into_root_schema_for::<GameState>();
+    serde_json::to_string_pretty(&schema).expect("schema serialization should always succeed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emit_schema_contains_types() {
+        let schema: serde_json::Value = serde_json::from_str(&emit_schema()).unwrap();
+        assert!(schema["properties"]["ships"].is_object());
+        for name in [
+            "Ship",
+            "Position",
+            "Direction",
+            "ShipClass",
+            "HitType",
+            "RoundCommit",
+        ] {
+            assert!(
+                schema["definitions"][name].is_object(),
+                "missing definition for {name}"
+            );
+        }
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::sha::Digest;
+
+use crate::{AiPlayer, Fleet, GameError, GameState, Position, RoundCommit, BOARD_SIZE};
+
+/// One of the two players in a [LocalMatch].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Player {
+    A,
+    B,
+}
+
+impl Player {
+    pub fn other(self) -> Self {
+        match self {
+            Self::A => Self::B,
+            Self::B => Self::A,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A match between two boards played entirely in-process, without running the zkVM.
+///
+/// Each shot is applied with [GameState::play_round], and the resulting commit is checked against
+/// the chain of state commitments for the defending board, as a verifier would check the journal
+/// of the round guest. This allows testing the flow of a full game, including self-play between
+/// computer players, without the cost of proving.
+#[derive(Clone, Debug)]
+pub struct LocalMatch {
+    boards: [GameState; 2],
+    /// The latest commitment to each board, as known to the other player.
+    commits: [Digest; 2],
+    to_move: Player,
+    turns: usize,
+    winner: Option<Player>,
+}
+
+impl LocalMatch {
+    /// Starts a match between the two boards, with [Player::A] shooting first.
+    pub fn new(board_a: GameState, board_b: GameState) -> Self {
+        let commits = [board_a.commit(), board_b.commit()];
+        Self {
+            boards: [board_a, board_b],
+            commits,
+            to_move: Player::A,
+            turns: 0,
+            winner: None,
+        }
+    }
+
+    /// The current board of the given player.
+    pub fn board(&self, player: Player) -> &GameState {
+        &self.boards[player.index()]
+    }
+
+    /// The latest commitment to the board of the given player, following the chain of rounds.
+    pub fn commit(&self, player: Player) -> Digest {
+        self.commits[player.index()]
+    }
+
+    /// The player taking the next shot.
+    pub fn to_move(&self) -> Player {
+        self.to_move
+    }
+
+    /// Number of shots taken so far by both players.
+    pub fn turns(&self) -> usize {
+        self.turns
+    }
+
+    /// The player who sank every ship of the other player, if the match is over.
+    pub fn winner(&self) -> Option<Player> {
+        self.winner
+    }
+
+    /// Applies a shot from the player to move against the board of the other player, checks the
+    /// round against the commitment to that board, and passes the turn to the other player.
+    ///
+    /// Returns [GameError::GameOver] if the match already has a winner, or
+    /// [GameError::ShotOutOfBounds] without passing the turn if the shot is off the board.
+    pub fn shoot(&mut self, shot: Position) -> Result<RoundCommit, GameError> {
+        if self.winner.is_some() {
+            return Err(GameError::GameOver);
+        }
+        let defender = self.to_move.other().index();
+        let round = self.boards[defender].play_round(shot)?;
+        self.commits[defender] = round.verify_transition(&self.commits[defender], shot)?;
+        self.turns += 1;
+        match round.game_over {
+            true => self.winner = Some(self.to_move),
+            false => self.to_move = self.to_move.other(),
+        }
+        Ok(round)
+    }
+
+    /// Plays the rest of the match between two [AiPlayer]s expecting the given fleet, returning
+    /// the winner. The players are deterministic, so the same boards always give the same result.
+    ///
+    /// Returns [GameError::TurnLimitExceeded] if the match does not end after each player has shot
+    /// every cell once, which can only happen if a board does not contain the given fleet.
+    pub fn play_ai(&mut self, fleet: &Fleet) -> Result<Player, GameError> {
+        let max_turns = 2 * BOARD_SIZE * BOARD_SIZE;
+        let mut players = [AiPlayer::new(fleet), AiPlayer::new(fleet)];
+        loop {
+            if let Some(winner) = self.winner {
+                return Ok(winner);
+            }
+            let player = &mut players[self.to_move.index()];
+            let shot = match player.next_shot() {
+                Some(shot) if self.turns < max_turns => shot,
+                _ => return Err(GameError::TurnLimitExceeded(max_turns)),
+            };
+            let round = self.shoot(shot)?;
+            player.record(shot, &round.hit);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rand"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ai_match_has_winner() {
+        let mut local_match = LocalMatch::new(rand::random(), rand::random());
+        let winner = local_match.play_ai(&Fleet::standard()).unwrap();
+
+        assert_eq!(local_match.winner(), Some(winner));
+        assert!(local_match.board(winner.other()).is_defeated());
+        assert!(!local_match.board(winner).is_defeated());
+        for player in [Player::A, Player::B] {
+            assert_eq!(
+                local_match.commit(player),
+                local_match.board(player).commit()
+            );
+        }
+        assert_eq!(
+            local_match.shoot(Position { x: 0, y: 0 }),
+            Err(GameError::GameOver)
+        );
+    }
+
+    #[test]
+    fn off_board_shot_rejected() {
+        let mut local_match = LocalMatch::new(rand::random(), rand::random());
+        let shot = Position { x: 10, y: 0 };
+        assert_eq!(
+            local_match.shoot(shot),
+            Err(GameError::ShotOutOfBounds(shot))
+        );
+        assert_eq!(local_match.to_move(), Player::A);
+        assert_eq!(local_match.turns(), 0);
+    }
+}
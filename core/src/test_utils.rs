@@ -0,0 +1,50 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Boards shared by the tests of each module.
+
+use crate::{Direction, GameState, Ship, ShipClass};
+
+/// The ships of the example board used by most tests.
+///
+/// ```text
+///  | 0 1 2 3 4 5 6 7 8 9 |
+/// 0|                     |
+/// 1|       B B B B       |
+/// 2|                     |
+/// 3|     A               |
+/// 4|     A               |
+/// 5|     A         S S S |
+/// 6|     A               |
+/// 7|     A   C     D D   |
+/// 8|         C           |
+/// 9|         C           |
+/// ```
+pub(crate) fn example_ships() -> Vec<Ship> {
+    vec![
+        Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+        Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
+        Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
+        Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+        Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+    ]
+}
+
+/// The example board with a random pepper and no shots applied.
+pub(crate) fn example_state() -> GameState {
+    GameState {
+        ships: example_ships(),
+        ..GameState::new(rand::random())
+    }
+}
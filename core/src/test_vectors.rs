@@ -0,0 +1,150 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fixed boards and the expected hex digests of their commitments. A change to any of these
+//! digests means the serialization, ordering, or hashing of [GameState] has changed, which breaks
+//! commitments made by earlier versions.
+
+use crate::{test_utils::example_state, Direction, GameState, Position, Ship, ShipClass};
+
+/// The board used by most tests, with no shots applied.
+fn basic() -> GameState {
+    GameState {
+        pepper: vec![0x42; 16],
+        ..example_state()
+    }
+}
+
+/// The basic board with a pepper of the default length, which is encoded with a length prefix.
+fn basic_long_pepper() -> GameState {
+    GameState {
+        pepper: vec![0x42; crate::PEPPER_LEN],
+        ..basic()
+    }
+}
+
+/// Shots of the example game, which sink the carrier, cruiser, and destroyer.
+fn example_shots() -> Vec<Position> {
+    [
+        (1, 1),
+        (2, 5),
+        (3, 5),
+        (2, 6),
+        (2, 7),
+        (2, 8),
+        (2, 4),
+        (2, 3),
+        (4, 9),
+        (4, 8),
+        (4, 7),
+        (7, 2),
+        (7, 7),
+        (6, 7),
+        (8, 7),
+    ]
+    .into_iter()
+    .map(Position::from)
+    .collect()
+}
+
+/// The board from the example game, after all of its shots.
+fn example_game() -> GameState {
+    let mut state = GameState {
+        pepper: vec![0x07; 16],
+        ..basic()
+    };
+    state.apply_shots(&example_shots());
+    state
+}
+
+/// A board with only some of the fleet placed, including a diagonal ship.
+fn partial_diagonal() -> GameState {
+    GameState {
+        ships: vec![
+            Ship::new(ShipClass::Carrier, (0, 0), Direction::DiagonalDown),
+            Ship::new(ShipClass::Destroyer, (5, 9), Direction::DiagonalUp).with_hit_mask(0x01),
+        ],
+        pepper: vec![0xa5; 16],
+    }
+}
+
+fn vectors() -> [(&'static str, GameState, &'static str, &'static str); 4] {
+    [
+        (
+            "basic",
+            basic(),
+            "3f887425d271624f1084b7e44b07a5ecb69c8eaf15d91f7f3e8f77234ef1d11f",
+            "cfd4438420386e37f1ab534068d9511f9d3a10ccc800a3b81dbe56f01029d6d3",
+        ),
+        (
+            "example_game",
+            example_game(),
+            "a201ea779e1e2ce9f3d5efa94f095228c4bfec9e905ed082d4ecd5e7fbcb3479",
+            "a6fd24245d9681bbef14bbe0d0e3e3ea0bdcca0e16550f929bbf986897cba45e",
+        ),
+        (
+            "partial_diagonal",
+            partial_diagonal(),
+            "464ab72710393ec4ece877bde10bb32463297823f46b45c5bf351f44c90b209a",
+            "8032523afb5f5acac6376491034cc9a8d35f2b6e8749736f264dd841bc638754",
+        ),
+        (
+            "basic_long_pepper",
+            basic_long_pepper(),
+            "a1f9acf68e74b1ba9c95c9245b6480e47a963c393a4b17d24d49c02da40376f5",
+            "0134f5aaa8fcdb0cad8d3910d04514a64819d11e72be6857e1b7ca552cd91f0a",
+        ),
+    ]
+}
+
+#[test]
+fn commit_matches_vectors() {
+    for (name, state, board, commit) in vectors() {
+        assert_eq!(state.board_digest().to_string(), board, "{name}");
+        assert_eq!(state.commit().to_string(), commit, "{name}");
+    }
+}
+
+#[test]
+fn apply_shots_matches_apply_shot() {
+    let shots = example_shots();
+    let mut expected_state = basic();
+    let expected: Vec<_> = shots
+        .iter()
+        .map(|&shot| expected_state.apply_shot(shot))
+        .collect();
+
+    let mut state = basic();
+    assert_eq!(state.apply_shots(&shots), expected);
+    assert_eq!(state, expected_state);
+    assert_eq!(basic().apply_shots_pure(&shots), expected_state);
+}
+
+/// Winner and total number of shots of a match between two computer players, on boards sampled
+/// from seeded generators. A change to the outcome means that the sampler, the computer player, or
+/// the application of shots has changed behavior.
+#[cfg(feature = "rand")]
+#[test]
+fn ai_match_matches_vector() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{Fleet, LocalMatch, Player};
+
+    let fleet = Fleet::standard();
+    let sample = |seed| GameState::sample_with(&mut StdRng::seed_from_u64(seed), &fleet, 100);
+    let mut local_match = LocalMatch::new(sample(1).unwrap(), sample(2).unwrap());
+    let winner = local_match.play_ai(&fleet).unwrap();
+
+    assert_eq!((winner, local_match.turns()), (Player::A, 95));
+}
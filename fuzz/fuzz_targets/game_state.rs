@@ -0,0 +1,41 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![no_main]
+
+use battleship_core::GameState;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Decoding as used when computing commitments. Any decoded state must be classified by check
+    // without panicking.
+    if let Ok(state) = bincode::deserialize::<GameState>(data) {
+        let _ = state.check();
+        let _ = state.check_partial();
+    }
+
+    // Decoding as used by env::read in the guests, which reads a stream of words.
+    let words: Vec<u32> = data
+        .chunks(4)
+        .map(|chunk| {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(word)
+        })
+        .collect();
+    if let Ok(state) = risc0_zkvm::serde::from_slice::<GameState, u32>(&words) {
+        let _ = state.check();
+        let _ = state.check_partial();
+    }
+});
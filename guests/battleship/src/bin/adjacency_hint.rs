@@ -0,0 +1,36 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::guest::env;
+
+use battleship_core::{AdjacencyHintCommit, AdjacencyHintInput};
+
+fn main() {
+    // Read in the current game state and the shot to apply.
+    let AdjacencyHintInput { mut state, shot } = env::read();
+
+    // Reject shots that are off the board, as in the round guest.
+    if let Err(err) = state.check_shot(shot, false) {
+        panic!("Invalid shot: {}", err);
+    }
+
+    // Apply the shot as in the round guest. Shots do not move ships, so the hint can be taken from
+    // the board after the shot.
+    let round_commit = state
+        .play_round(shot)
+        .expect("checked shot should be on the board");
+
+    // Commit the result, with whether a missed shot landed next to a ship.
+    env::commit(&AdjacencyHintCommit::from_round(&round_commit, &state));
+}
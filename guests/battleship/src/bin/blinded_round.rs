@@ -0,0 +1,47 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::guest::env;
+
+use battleship_core::{BlindedRoundInput, RoundInput};
+
+fn main() {
+    // Read in the round, as read by the round guest, and the nonce to blind its result with.
+    let BlindedRoundInput {
+        round:
+            RoundInput {
+                mut state,
+                shot,
+                strict,
+                context,
+            },
+        nonce,
+    } = env::read();
+
+    // Reject shots that are off the board, or that target a sunk ship under strict rules, rather
+    // than committing a misleading result for them.
+    if let Err(err) = state.check_shot(shot, strict) {
+        panic!("Invalid shot: {}", err);
+    }
+
+    // Apply the shot as the round guest does, bound to the match context.
+    let round_commit = state
+        .play_round(shot)
+        .expect("checked shot should be on the board")
+        .with_context(context);
+
+    // Commit only a digest of the shot and result, alongside the state commitments. They are
+    // revealed separately.
+    env::commit(&round_commit.blind(nonce));
+}
@@ -0,0 +1,34 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::guest::env;
+
+use battleship_core::{ConsistencyCommit, ConsistencyInput};
+
+fn main() {
+    // Read in the board and the results observed by the other player.
+    let ConsistencyInput { state, fog } = env::read();
+
+    // Check that all ships are placed, all ships and in bounds, and no ships overlap.
+    if !state.check() {
+        panic!("Invalid GameState");
+    }
+
+    // Commit the board and whether it matches every observation, to be read by the verifier.
+    env::commit(&ConsistencyCommit {
+        state: state.commit(),
+        consistent: fog.is_consistent_with(&state),
+        fog,
+    });
+}
@@ -0,0 +1,39 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::guest::env;
+
+use battleship_core::{validate_shot_sequence, DamageReportCommit, DamageReportInput};
+
+fn main() {
+    // Read in the current game state and the batch of shots to apply.
+    let DamageReportInput { mut state, shots } = env::read();
+    if let Err(err) = validate_shot_sequence(&shots) {
+        panic!("Invalid shots: {}", err);
+    }
+
+    // Apply each shot in order as in the round guest, keeping the per-shot results private.
+    let old_state = state.commit();
+    let rounds: Vec<_> = shots
+        .iter()
+        .map(|&shot| {
+            state
+                .play_round(shot)
+                .expect("validated shot should be on the board")
+        })
+        .collect();
+
+    // Commit only the totals to be read by the verifier.
+    env::commit(&DamageReportCommit::from_rounds(old_state, shots, &rounds));
+}
@@ -0,0 +1,38 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::guest::env;
+
+use battleship_core::{ForbiddenZoneCommit, ForbiddenZoneInput};
+
+fn main() {
+    // Read in an initial game state supplied by the player, and the cells the rules forbid.
+    let ForbiddenZoneInput { state, forbidden } = env::read();
+
+    // Check that all ships are placed, all ships and in bounds, and no ships overlap.
+    if !state.check() {
+        panic!("Invalid GameState");
+    }
+
+    // Check that no ship occupies a forbidden cell.
+    if !state.avoids(&forbidden) {
+        panic!("Ship occupies a forbidden cell");
+    }
+
+    // Commit to the board and the forbidden cells it was checked against, for the verifier to read.
+    env::commit(&ForbiddenZoneCommit {
+        state: state.commit(),
+        forbidden: forbidden.digest(),
+    });
+}
@@ -12,19 +12,35 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use risc0_zkvm::guest::env;
+use risc0_zkvm::{guest::env, sha::Digest};
 
-use battleship_core::GameState;
+use battleship_core::{Fleet, GameConfig, GameState, InitCommit};
 
 fn main() {
-    // Read in an initial game state supplied by the player.
+    // Read in an initial game state supplied by the player, the fleet agreed on for the match,
+    // the key for the layout fingerprint if the coordinator requested one, and the digest of the
+    // match context.
     let state: GameState = env::read();
+    let fleet: Fleet = env::read();
+    let layout_key: Option<Digest> = env::read();
+    let context: Option<Digest> = env::read();
 
-    // Check that all ships are placed, all ships and in bounds, and no ships overlap.
-    if !state.check() {
+    // Check that all ships in the fleet are placed, all ships and in bounds, and no ships overlap.
+    let config = GameConfig {
+        fleet,
+        ..Default::default()
+    };
+    if !state.check_with(&config) {
         panic!("Invalid GameState");
     }
 
-    // Write a commitment to the game state to the journal for the verifier to read.
-    env::commit(&state.commit());
+    // Write a commitment to the game state and the fleet to the journal for the verifier to read.
+    // The layout fingerprint does not depend on the pepper, so the coordinator holding the key can
+    // link identical layouts, but without the key it reveals nothing about the board.
+    env::commit(&InitCommit {
+        state: state.commit(),
+        fleet: config.fleet.digest(),
+        layout: layout_key.map(|key| state.layout_fingerprint(&key)),
+        context,
+    });
 }
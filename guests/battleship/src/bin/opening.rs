@@ -0,0 +1,54 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::guest::env;
+
+use battleship_core::{validate_shot_sequence, OpeningCommit, OpeningInput};
+
+fn main() {
+    // Read in an initial game state supplied by the player, the first shots to apply, and the
+    // digest of the match context.
+    let OpeningInput {
+        mut state,
+        shots,
+        context,
+    } = env::read();
+
+    // Check that all ships are placed, all ships and in bounds, and no ships overlap.
+    if !state.check() {
+        panic!("Invalid GameState");
+    }
+    if let Err(err) = validate_shot_sequence(&shots) {
+        panic!("Invalid shots: {}", err);
+    }
+    let initial_state_commit = state.commit();
+
+    // Apply each shot in order, chaining the state commitments from one round to the next. This
+    // is equivalent to running init followed by round once per shot, but in a single execution.
+    let rounds = shots
+        .into_iter()
+        .map(|shot| {
+            state
+                .play_round(shot)
+                .expect("validated shot should be on the board")
+                .with_context(context)
+        })
+        .collect();
+
+    // Commit the results to be read by the verifier.
+    env::commit(&OpeningCommit {
+        state: initial_state_commit,
+        rounds,
+    });
+}
@@ -0,0 +1,37 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::guest::env;
+
+use battleship_core::{RematchCommit, RematchInput};
+
+fn main() {
+    // Read in the new game state, and the layout of the board from the previous game.
+    let RematchInput {
+        state,
+        prior_layout,
+    } = env::read();
+
+    // Check that the new board is valid, as in the init guest.
+    if !state.check() {
+        panic!("Invalid GameState");
+    }
+
+    // Write a commitment to the new board, along with whether it reuses the previous layout.
+    env::commit(&RematchCommit {
+        state: state.commit(),
+        prior_layout,
+        is_fresh: state.layout_digest() != prior_layout,
+    });
+}
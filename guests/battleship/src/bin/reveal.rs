@@ -0,0 +1,32 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::guest::env;
+
+use battleship_core::{RevealCommit, RevealInput};
+
+fn main() {
+    // Read in the revealed board, and whether to disclose its layout digest.
+    let RevealInput {
+        state,
+        disclose_layout,
+    } = env::read();
+
+    // Commit only the digest of the board, which the verifier compares against the commitment it
+    // stored, so that verifying the reveal needs no more than comparing two digests.
+    env::commit(&RevealCommit {
+        board_commit: state.commit(),
+        layout: disclose_layout.then(|| state.layout_digest()),
+    });
+}
@@ -14,23 +14,32 @@
 
 use risc0_zkvm::guest::env;
 
-use battleship_core::{RoundCommit, RoundInput};
+use battleship_core::RoundInput;
 
 fn main() {
-    // Read in the current same state and the shot to apply.
-    let RoundInput { mut state, shot } = env::read();
+    // Read in the current same state, the shot to apply, whether shots at sunk ships are
+    // rejected, and the digest of the match context.
+    let RoundInput {
+        mut state,
+        shot,
+        strict,
+        context,
+    } = env::read();
+
+    // Reject shots that are off the board, or that target a sunk ship under strict rules, rather
+    // than committing a misleading result for them.
+    if let Err(err) = state.check_shot(shot, strict) {
+        panic!("Invalid shot: {}", err);
+    }
 
     // Commit to the state before applying the shot, apply the shot and then commit to the state
-    // after applying the shot.
-    let old_state_commit = state.commit();
-    let hit = state.apply_shot(shot);
-    let new_state_commit = state.commit();
+    // after applying the shot. If the shot sunk a ship, the cells it occupied are also revealed.
+    // The round is bound to the match context, so that the proof cannot be reused in another match.
+    let round_commit = state
+        .play_round(shot)
+        .expect("checked shot should be on the board")
+        .with_context(context);
 
     // Commit the results to be read by the verifier.
-    env::commit(&RoundCommit {
-        old_state: old_state_commit,
-        new_state: new_state_commit,
-        shot,
-        hit,
-    });
+    env::commit(&round_commit);
 }
@@ -0,0 +1,34 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::guest::env;
+
+use battleship_core::{GameState, MatchSetup};
+
+fn main() {
+    // Read in the initial game state of each player.
+    let state_a: GameState = env::read();
+    let state_b: GameState = env::read();
+
+    // Check that each board is valid, and that the fleets can share the ocean without overlapping.
+    if !state_a.check_shared_ocean(&state_b) {
+        panic!("Invalid GameState for shared ocean");
+    }
+
+    // Write a commitment to each game state to the journal for the verifier to read.
+    env::commit(&MatchSetup {
+        player_a_commit: state_a.commit(),
+        player_b_commit: state_b.commit(),
+    });
+}
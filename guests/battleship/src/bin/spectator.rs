@@ -0,0 +1,39 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risc0_zkvm::guest::env;
+
+use battleship_core::{SpectatorCommit, SpectatorInput};
+
+fn main() {
+    // Read in the current game state, the shot to apply, and the number of hits so far.
+    let SpectatorInput {
+        mut state,
+        shot,
+        hits,
+    } = env::read();
+
+    // Reject shots that are off the board, as in the round guest.
+    if let Err(err) = state.check_shot(shot, false) {
+        panic!("Invalid shot: {}", err);
+    }
+
+    // Apply the shot as in the round guest, but only disclose whether it was a hit.
+    let round_commit = state
+        .play_round(shot)
+        .expect("checked shot should be on the board");
+
+    // Commit the results to be read by spectators.
+    env::commit(&SpectatorCommit::from_round(&round_commit, hits));
+}
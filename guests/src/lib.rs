@@ -1 +1,3 @@
 include!(concat!(env!("OUT_DIR"), "/methods.rs"));
+
+pub mod verify;
@@ -0,0 +1,50 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Image IDs of the guests that receipts from an opponent are verified against. These are the
+//! anchor of trust for every proof in a game. They are the constants generated by the build
+//! script, exposed as [Digest]s under names that are part of this crate's API, so that verifiers
+//! do not depend on the names and word arrays the build script generates.
+
+use risc0_zkvm::sha::Digest;
+
+use crate::{INIT_ID, OPENING_ID, ROUND_ID};
+
+/// Image ID of the init guest, which proves that a board is valid for the fleet of the match.
+pub fn init_image_id() -> Digest {
+    Digest::from(INIT_ID)
+}
+
+/// Image ID of the opening guest, which proves that a board is valid with the opening shots
+/// applied.
+pub fn opening_image_id() -> Digest {
+    Digest::from(OPENING_ID)
+}
+
+/// Image ID of the round guest, which proves the result of applying a shot to a board.
+pub fn round_image_id() -> Digest {
+    Digest::from(ROUND_ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_ids_match_generated() {
+        assert_eq!(init_image_id().as_words(), INIT_ID);
+        assert_eq!(opening_image_id().as_words(), OPENING_ID);
+        assert_eq!(round_image_id().as_words(), ROUND_ID);
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{AdjacencyHintCommit, AdjacencyHintInput, HitType, Position};
+use battleship_guests::ADJACENCY_HINT_ELF;
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+use common::example_state;
+
+#[test]
+fn miss_next_to_carrier() -> anyhow::Result<()> {
+    let mut state = example_state();
+
+    for (shot, hit, adjacent_to_ship) in [
+        ((1, 5), HitType::Miss, true),
+        ((0, 0), HitType::Miss, false),
+        ((2, 5), HitType::Hit, false),
+    ] {
+        let shot = Position::from(shot);
+        let input = AdjacencyHintInput {
+            state: state.clone(),
+            shot,
+        };
+        let env = ExecutorEnv::builder().write(&input)?.build()?;
+        let execution = default_executor().execute(env, ADJACENCY_HINT_ELF)?;
+
+        let old_state = state.commit();
+        state.apply_shot(shot);
+        assert_eq!(
+            execution.journal.decode::<AdjacencyHintCommit>()?,
+            AdjacencyHintCommit {
+                old_state,
+                new_state: state.commit(),
+                shot,
+                hit,
+                adjacent_to_ship,
+            }
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn off_board_shot_rejected() -> anyhow::Result<()> {
+    let input = AdjacencyHintInput {
+        state: example_state(),
+        shot: Position { x: 10, y: 5 },
+    };
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    assert!(default_executor().execute(env, ADJACENCY_HINT_ELF).is_err());
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{BlindedRoundCommit, BlindedRoundInput, GameState, Position, RoundInput};
+use battleship_guests::BLINDED_ROUND_ELF;
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+use common::example_state;
+
+fn execute_blinded_round(
+    state: &GameState,
+    shot: Position,
+    nonce: [u8; 16],
+) -> anyhow::Result<BlindedRoundCommit> {
+    let input = BlindedRoundInput {
+        round: RoundInput {
+            state: state.clone(),
+            shot,
+            strict: false,
+            context: None,
+        },
+        nonce,
+    };
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    let execution = default_executor().execute(env, BLINDED_ROUND_ELF)?;
+    Ok(execution.journal.decode()?)
+}
+
+#[test]
+fn blinded_round() -> anyhow::Result<()> {
+    let state = example_state();
+    let shot = Position { x: 2, y: 5 };
+    let nonce = rand::random();
+    let blinded = execute_blinded_round(&state, shot, nonce)?;
+
+    // The journal only contains the state commitments and a digest of the outcome.
+    let round_commit = state.clone().play_round(shot).unwrap();
+    assert_eq!(blinded, round_commit.blind(nonce));
+
+    // Repeating the shot with a fresh nonce gives an unrelated outcome digest.
+    let reblinded = execute_blinded_round(&state, shot, rand::random())?;
+    assert_ne!(reblinded.outcome, blinded.outcome);
+
+    // The reveal opens the blinded commit to the full round commit.
+    let opened = blinded.open(&round_commit.reveal(nonce));
+    assert_eq!(opened, Some(round_commit));
+
+    Ok(())
+}
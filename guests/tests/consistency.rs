@@ -0,0 +1,57 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{
+    ConsistencyCommit, ConsistencyInput, Direction, FogBoard, GameState, Position, Ship, ShipClass,
+};
+use battleship_guests::CONSISTENCY_ELF;
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+use common::example_state;
+
+fn execute_consistency(state: &GameState, fog: &FogBoard) -> anyhow::Result<ConsistencyCommit> {
+    let input = ConsistencyInput {
+        state: state.clone(),
+        fog: fog.clone(),
+    };
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    let execution = default_executor().execute(env, CONSISTENCY_ELF)?;
+    Ok(execution.journal.decode()?)
+}
+
+#[test]
+fn board_consistent_with_observations() -> anyhow::Result<()> {
+    let mut state = example_state();
+    let mut fog = FogBoard::new();
+    for shot in [(1, 1), (2, 5), (7, 7), (8, 7), (6, 7)] {
+        let shot = Position::from(shot);
+        fog.record(shot, &state.apply_shot(shot));
+    }
+
+    let commit = execute_consistency(&state, &fog)?;
+    assert_eq!(commit.state, state.commit());
+    assert_eq!(commit.fog, fog);
+    assert!(commit.consistent);
+
+    // Moving the carrier off the hit at (2, 5) no longer matches the observations.
+    let mut moved = state.clone();
+    moved.ships[0] = Ship::new(ShipClass::Carrier, (0, 3), Direction::Vertical);
+    let commit = execute_consistency(&moved, &fog)?;
+    assert_eq!(commit.state, moved.commit());
+    assert!(!commit.consistent);
+
+    Ok(())
+}
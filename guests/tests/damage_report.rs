@@ -0,0 +1,54 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{DamageReportCommit, DamageReportInput, Position};
+use battleship_guests::DAMAGE_REPORT_ELF;
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+use common::{example_state, journal_bytes};
+
+#[test]
+fn damage_report_journal_hides_results() -> anyhow::Result<()> {
+    let mut state = example_state();
+    let shots: Vec<Position> = [(7, 7), (1, 1), (8, 7), (2, 3), (9, 9)]
+        .map(Position::from)
+        .to_vec();
+
+    let input = DamageReportInput {
+        state: state.clone(),
+        shots: shots.clone(),
+    };
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    let execution = default_executor().execute(env, DAMAGE_REPORT_ELF)?;
+
+    let old_state = state.commit();
+    for &shot in shots.iter() {
+        state.apply_shot(shot);
+    }
+    let expected = DamageReportCommit {
+        old_state,
+        new_state: state.commit(),
+        shots,
+        hits: 3,
+        sinks: 1,
+    };
+
+    // The journal contains exactly the damage report, which has the shots and the totals but no
+    // field for the result of each shot.
+    let expected_journal = journal_bytes(&expected)?;
+    assert_eq!(execution.journal.bytes, expected_journal);
+    Ok(())
+}
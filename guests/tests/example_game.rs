@@ -12,31 +12,56 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod common;
+
 use battleship_core::{
-    Direction, GameState, HitType, Position, RoundCommit, RoundInput, Ship, ShipClass,
+    Fleet, GameState, HitType, InitCommit, Position, RoundCommit, RoundInput, ShipClass,
 };
 use battleship_guests::{INIT_ELF, ROUND_ELF};
-use risc0_zkvm::{default_executor, ExecutorEnv};
+use risc0_zkvm::{default_executor, sha::Digest, ExecutorEnv};
+
+use common::{example_ships, example_state};
 
-// Run the round function once for each round and confirm the state evolves as expected.
-fn run_round(state: &mut GameState, shot: Position, hit_expected: HitType) -> anyhow::Result<()> {
+// Run the round function once for each round and confirm the state evolves as expected, returning
+// the commit for the round.
+fn run_round(
+    state: &mut GameState,
+    shot: Position,
+    hit_expected: HitType,
+) -> anyhow::Result<RoundCommit> {
     let input = RoundInput {
         state: state.clone(),
         shot,
+        strict: false,
+        context: None,
     };
     let input_state_commit = state.commit();
     let env = ExecutorEnv::builder().write(&input)?.build()?;
     let execution = default_executor().execute(env, ROUND_ELF)?;
+    let journal: RoundCommit = execution.journal.decode()?;
     state.apply_shot(shot);
+
+    // The cell under the shot should open consistently with the expected result.
+    assert!(journal.opening.check(
+        journal.shot,
+        &hit_expected,
+        &input_state_commit,
+        &state.commit()
+    ));
+
     let commit = RoundCommit {
-        shot,
+        shot: shot.try_into()?,
+        sunk_cells: state.sunk_cells(&hit_expected),
         hit: hit_expected,
         old_state: input_state_commit,
         new_state: state.commit(),
+        game_over: state.is_defeated(),
+        opening: journal.opening.clone(),
+        context: None,
     };
-    assert_eq!(commit, execution.journal.decode()?);
+    assert_eq!(commit, journal);
 
-    Ok(())
+    Ok(journal)
 }
 
 #[test]
@@ -53,22 +78,24 @@ fn exmaple_game() -> anyhow::Result<()> {
     // 7|     A   C     D D   |
     // 8|         C           |
     // 9|         C           |
-    let mut state = GameState {
-        ships: vec![
-            Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
-            Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
-            Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
-            Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
-            Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
-        ],
-        pepper: rand::random(),
-    };
+    let mut state = example_state();
 
     // Create a RISC Zero executor, which is a RISC-V emulator with support for RISC Zero syscalls.
     // Use it to run the init program to create a committment to a state with verified validity.
-    let env = ExecutorEnv::builder().write(&state)?.build()?;
+    let env = ExecutorEnv::builder()
+        .write(&state)?
+        .write(&Fleet::standard())?
+        .write(&None::<Digest>)?
+        .write(&None::<Digest>)?
+        .build()?;
     let execution = default_executor().execute(env, INIT_ELF)?;
-    assert_eq!(state.commit(), execution.journal.decode()?);
+    let expected = InitCommit {
+        state: state.commit(),
+        fleet: Fleet::standard().digest(),
+        layout: None,
+        context: None,
+    };
+    assert_eq!(expected, execution.journal.decode()?);
 
     // Example player takes their first shot and misses.
     run_round(&mut state, Position { x: 1, y: 1 }, HitType::Miss)?;
@@ -108,21 +135,97 @@ fn exmaple_game() -> anyhow::Result<()> {
     // Example player finds and sinks the submarine.
     run_round(&mut state, Position { x: 8, y: 5 }, HitType::Hit)?;
     run_round(&mut state, Position { x: 7, y: 5 }, HitType::Hit)?;
-    run_round(
+    let round = run_round(
         &mut state,
         Position { x: 9, y: 5 },
         HitType::Sunk(ShipClass::Submarine),
     )?;
+    assert!(!round.game_over);
 
     // Example player finds and sinks the battleship.
     run_round(&mut state, Position { x: 3, y: 1 }, HitType::Hit)?;
     run_round(&mut state, Position { x: 4, y: 1 }, HitType::Hit)?;
     run_round(&mut state, Position { x: 5, y: 1 }, HitType::Hit)?;
-    run_round(
+    let last_round = run_round(
         &mut state,
         Position { x: 6, y: 1 },
         HitType::Sunk(ShipClass::Battleship),
     )?;
 
+    // Only sinking the last ship ends the game.
+    assert!(last_round.game_over);
+
+    Ok(())
+}
+
+#[test]
+fn example_game_ends_in_victory() -> anyhow::Result<()> {
+    // A fixed pepper keeps every commitment in the game the same from run to run.
+    let mut state = GameState {
+        ships: example_ships(),
+        ..GameState::new([7; 32])
+    };
+    let env = ExecutorEnv::builder()
+        .write(&state)?
+        .write(&Fleet::standard())?
+        .write(&None::<Digest>)?
+        .write(&None::<Digest>)?
+        .build()?;
+    let init_commit: InitCommit = default_executor()
+        .execute(env, INIT_ELF)?
+        .journal
+        .decode()?;
+
+    // Every shot of the example game, ending with the sinking of the battleship.
+    let shots = [
+        (1, 1),
+        (2, 5),
+        (3, 5),
+        (2, 6),
+        (2, 7),
+        (2, 8),
+        (2, 4),
+        (2, 3),
+        (4, 9),
+        (4, 8),
+        (4, 7),
+        (7, 2),
+        (7, 7),
+        (6, 7),
+        (8, 7),
+        (8, 5),
+        (7, 5),
+        (9, 5),
+        (3, 1),
+        (4, 1),
+        (5, 1),
+        (6, 1),
+    ];
+    let mut state_commit = init_commit.state;
+    let mut game_over = false;
+    for (i, shot) in shots.into_iter().enumerate() {
+        let shot = Position::from(shot);
+        let input = RoundInput {
+            state: state.clone(),
+            shot,
+            strict: false,
+            context: None,
+        };
+        let env = ExecutorEnv::builder().write(&input)?.build()?;
+        let round: RoundCommit = default_executor()
+            .execute(env, ROUND_ELF)?
+            .journal
+            .decode()?;
+        state.apply_shot(shot);
+
+        // Each round continues the chain from the init commitment, and only the last ends it.
+        state_commit = round.verify_transition(&state_commit, shot)?;
+        assert_eq!(round.game_over, i == shots.len() - 1, "round {i}");
+        game_over = round.game_over;
+    }
+
+    assert!(state.is_defeated());
+    assert!(game_over);
+    assert_eq!(state_commit, state.commit());
     Ok(())
 }
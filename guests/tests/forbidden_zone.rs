@@ -0,0 +1,53 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{CellMask, ForbiddenZoneCommit, ForbiddenZoneInput, GameState, Position};
+use battleship_guests::FORBIDDEN_ZONE_ELF;
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+use common::example_state;
+
+fn execute_forbidden_zone(
+    state: &GameState,
+    forbidden: CellMask,
+) -> anyhow::Result<ForbiddenZoneCommit> {
+    let input = ForbiddenZoneInput {
+        state: state.clone(),
+        forbidden,
+    };
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    let execution = default_executor().execute(env, FORBIDDEN_ZONE_ELF)?;
+    Ok(execution.journal.decode()?)
+}
+
+#[test]
+fn board_avoids_center() -> anyhow::Result<()> {
+    let state = example_state();
+    let center = CellMask::from_positions(Position::range((4, 4), (5, 5)));
+
+    let commit = execute_forbidden_zone(&state, center)?;
+    assert_eq!(commit.state, state.commit());
+    assert_eq!(commit.forbidden, center.digest());
+
+    Ok(())
+}
+
+#[test]
+fn board_in_forbidden_zone() {
+    // The cruiser at (4, 7) runs through the forbidden cell at (4, 8).
+    let forbidden = CellMask::from_positions([Position { x: 4, y: 8 }]);
+    assert!(execute_forbidden_zone(&example_state(), forbidden).is_err());
+}
@@ -0,0 +1,57 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{HiddenFleetCommit, HiddenFleetInput, HiddenOutcome, Position};
+use battleship_guests::HIDDEN_FLEET_ELF;
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+use common::{example_state, journal_bytes};
+
+#[test]
+fn hidden_fleet_journal_reveals_count_only() -> anyhow::Result<()> {
+    let mut state = example_state();
+
+    for (shot, outcome, ships_remaining) in [
+        ((7, 7), HiddenOutcome::Hit, 5),
+        ((1, 1), HiddenOutcome::Miss, 5),
+        ((8, 7), HiddenOutcome::SunkSomething, 4),
+    ] {
+        let shot = Position::from(shot);
+        let input = HiddenFleetInput {
+            state: state.clone(),
+            shot,
+        };
+        let env = ExecutorEnv::builder().write(&input)?.build()?;
+        let execution = default_executor().execute(env, HIDDEN_FLEET_ELF)?;
+
+        let old_state = state.commit();
+        state.apply_shot(shot);
+        let expected = HiddenFleetCommit {
+            old_state,
+            new_state: state.commit(),
+            shot,
+            outcome,
+            ships_remaining,
+        };
+
+        // The journal contains exactly the hidden fleet commit, which reports the number of ships
+        // remaining but has no field for the class of the ship that was sunk.
+        let expected_journal = journal_bytes(&expected)?;
+        assert_eq!(execution.journal.bytes, expected_journal);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{Fleet, GameState, InitCommit};
+use battleship_guests::INIT_ELF;
+use risc0_zkvm::{default_executor, sha::Digest, ExecutorEnv};
+
+use common::example_state;
+
+fn execute_init(state: &GameState, layout_key: Option<Digest>) -> anyhow::Result<InitCommit> {
+    let env = ExecutorEnv::builder()
+        .write(state)?
+        .write(&Fleet::standard())?
+        .write(&layout_key)?
+        .write(&None::<Digest>)?
+        .build()?;
+    let execution = default_executor().execute(env, INIT_ELF)?;
+    Ok(execution.journal.decode()?)
+}
+
+#[test]
+fn same_layout_different_pepper() -> anyhow::Result<()> {
+    let state_a = example_state();
+    let state_b = example_state();
+    assert_ne!(state_a.pepper, state_b.pepper);
+
+    let key = Digest::from(rand::random::<[u32; 8]>());
+    let commit_a = execute_init(&state_a, Some(key))?;
+    let commit_b = execute_init(&state_b, Some(key))?;
+
+    // The state commitments differ, but the fingerprints under the same key match.
+    assert_ne!(commit_a.state, commit_b.state);
+    assert_eq!(commit_a.layout, Some(state_a.layout_fingerprint(&key)));
+    assert_eq!(commit_a.layout, commit_b.layout);
+
+    Ok(())
+}
+
+#[test]
+fn fingerprint_hides_layout() -> anyhow::Result<()> {
+    let state = example_state();
+    let key = Digest::from(rand::random::<[u32; 8]>());
+    let layout = execute_init(&state, Some(key))?
+        .layout
+        .expect("layout fingerprint should be committed");
+
+    // Without the key, the fingerprint cannot be matched against a guessed layout.
+    assert_ne!(layout.fingerprint, state.layout_digest());
+    let other_key = Digest::from(rand::random::<[u32; 8]>());
+    assert_ne!(layout, state.layout_fingerprint(&other_key));
+    Ok(())
+}
+
+#[test]
+fn layout_not_disclosed() -> anyhow::Result<()> {
+    let commit = execute_init(&example_state(), None)?;
+    assert_eq!(commit.layout, None);
+    Ok(())
+}
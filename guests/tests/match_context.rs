@@ -0,0 +1,79 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{
+    Fleet, GameError, InitCommit, MatchContext, Position, RoundCommit, RoundInput,
+};
+use battleship_guests::{INIT_ELF, ROUND_ELF};
+use risc0_zkvm::{default_executor, sha::Digest, ExecutorEnv};
+
+use common::example_state;
+
+fn context(match_id: &str) -> Digest {
+    MatchContext {
+        players: vec!["alice".to_string(), "bob".to_string()],
+        match_id: match_id.to_string(),
+        timestamp: 1_700_000_000,
+    }
+    .digest()
+}
+
+#[test]
+fn proof_for_other_match_rejected() -> anyhow::Result<()> {
+    let state = example_state();
+    let agreed = context("final");
+    let other = context("semifinal");
+
+    let env = ExecutorEnv::builder()
+        .write(&state)?
+        .write(&Fleet::standard())?
+        .write(&None::<Digest>)?
+        .write(&Some(other))?
+        .build()?;
+    let init_commit: InitCommit = default_executor()
+        .execute(env, INIT_ELF)?
+        .journal
+        .decode()?;
+    assert_eq!(init_commit.check_context(Some(&other)), Ok(()));
+    assert_eq!(
+        init_commit.check_context(Some(&agreed)),
+        Err(GameError::ContextMismatch)
+    );
+
+    // A round proven for another match is rejected, even though it continues from the same state.
+    let input = RoundInput {
+        state: state.clone(),
+        shot: Position { x: 1, y: 1 },
+        strict: false,
+        context: Some(other),
+    };
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    let round_commit: RoundCommit = default_executor()
+        .execute(env, ROUND_ELF)?
+        .journal
+        .decode()?;
+    assert_eq!(round_commit.old_state, init_commit.state);
+    assert_eq!(
+        round_commit.check_context(Some(&agreed)),
+        Err(GameError::ContextMismatch)
+    );
+    assert_eq!(
+        round_commit.check_context(None),
+        Err(GameError::ContextMismatch)
+    );
+    assert_eq!(round_commit.check_context(Some(&other)), Ok(()));
+    Ok(())
+}
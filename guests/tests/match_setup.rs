@@ -0,0 +1,121 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{Fleet, GameState, MatchSetup, Position, RoundInput, ShipClass};
+use battleship_guests::{INIT_ELF, INIT_ID, ROUND_ELF};
+use risc0_zkvm::{default_prover, sha::Digest, ExecutorEnv, Receipt};
+
+use common::example_state;
+
+fn prove_init(state: &GameState) -> anyhow::Result<Receipt> {
+    prove_init_with_fleet(state, &Fleet::standard())
+}
+
+fn prove_init_with_fleet(state: &GameState, fleet: &Fleet) -> anyhow::Result<Receipt> {
+    let env = ExecutorEnv::builder()
+        .write(state)?
+        .write(fleet)?
+        .write(&None::<Digest>)?
+        .write(&None::<Digest>)?
+        .build()?;
+    Ok(default_prover().prove(env, INIT_ELF)?.receipt)
+}
+
+#[test]
+fn match_setup_from_valid_receipts() -> anyhow::Result<()> {
+    let state_a = example_state();
+    let state_b = example_state();
+
+    let setup =
+        MatchSetup::from_init_receipts(&prove_init(&state_a)?, &prove_init(&state_b)?, INIT_ID)?;
+    assert_eq!(setup.player_a_commit, state_a.commit());
+    assert_eq!(setup.player_b_commit, state_b.commit());
+
+    Ok(())
+}
+
+#[test]
+fn match_setup_rejects_mismatched_fleets() -> anyhow::Result<()> {
+    let state_a = example_state();
+
+    // Player B plays without a destroyer, which is a valid board for a different fleet.
+    let mut state_b = example_state();
+    state_b.ships.pop();
+    let fleet_b = Fleet {
+        classes: ShipClass::list()[..4].to_vec(),
+    };
+
+    let result = MatchSetup::from_init_receipts(
+        &prove_init(&state_a)?,
+        &prove_init_with_fleet(&state_b, &fleet_b)?,
+        INIT_ID,
+    );
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn match_setup_with_handicap_fleet() -> anyhow::Result<()> {
+    // Player A plays the classic fleet, while player B plays without a carrier.
+    let state_a = example_state();
+    let mut state_b = example_state();
+    state_b.ships.remove(0);
+    let fleet_a = Fleet::standard();
+    let fleet_b = Fleet {
+        classes: ShipClass::list()[1..].to_vec(),
+    };
+    let receipt_a = prove_init_with_fleet(&state_a, &fleet_a)?;
+    let receipt_b = prove_init_with_fleet(&state_b, &fleet_b)?;
+
+    let setup = MatchSetup::from_init_receipts_with_fleets(
+        (&receipt_a, &fleet_a),
+        (&receipt_b, &fleet_b),
+        INIT_ID,
+    )?;
+    assert_eq!(setup.player_a_commit, state_a.commit());
+    assert_eq!(setup.player_b_commit, state_b.commit());
+
+    // Each board is checked against the fleet agreed for that player, not the other.
+    let result = MatchSetup::from_init_receipts_with_fleets(
+        (&receipt_a, &fleet_b),
+        (&receipt_b, &fleet_a),
+        INIT_ID,
+    );
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn match_setup_rejects_non_init_receipt() -> anyhow::Result<()> {
+    let state = example_state();
+
+    // A receipt for a round, rather than init, does not attest to a valid board.
+    let input = RoundInput {
+        state: state.clone(),
+        shot: Position { x: 1, y: 1 },
+        strict: false,
+        context: None,
+    };
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    let round_receipt = default_prover().prove(env, ROUND_ELF)?.receipt;
+
+    let result = MatchSetup::from_init_receipts(&prove_init(&state)?, &round_receipt, INIT_ID);
+    assert!(result.is_err());
+
+    Ok(())
+}
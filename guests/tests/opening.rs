@@ -0,0 +1,88 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{
+    Fleet, InitCommit, OpeningCommit, OpeningInput, Position, RoundCommit, RoundInput,
+};
+use battleship_guests::{INIT_ELF, OPENING_ELF, ROUND_ELF};
+use risc0_zkvm::{default_executor, sha::Digest, ExecutorEnv, SessionInfo};
+
+use common::example_state;
+
+// Total cycles to prove the session, including padding of each segment to a power of two.
+fn padded_cycles(session: &SessionInfo) -> u64 {
+    session.segments.iter().map(|s| 1u64 << s.po2).sum()
+}
+
+#[test]
+fn opening_matches_separate_rounds() -> anyhow::Result<()> {
+    let state = example_state();
+    let shots = vec![
+        Position { x: 1, y: 1 },
+        Position { x: 7, y: 7 },
+        Position { x: 8, y: 7 },
+    ];
+
+    // Run init and then each round as separate executions.
+    let env = ExecutorEnv::builder()
+        .write(&state)?
+        .write(&Fleet::standard())?
+        .write(&None::<Digest>)?
+        .write(&None::<Digest>)?
+        .build()?;
+    let init = default_executor().execute(env, INIT_ELF)?;
+    let initial_state_commit = init.journal.decode::<InitCommit>()?.state;
+    let mut separate_cycles = padded_cycles(&init);
+
+    let mut round_state = state.clone();
+    let mut rounds = Vec::new();
+    for shot in shots.iter() {
+        let input = RoundInput {
+            state: round_state.clone(),
+            shot: *shot,
+            strict: false,
+            context: None,
+        };
+        let env = ExecutorEnv::builder().write(&input)?.build()?;
+        let round = default_executor().execute(env, ROUND_ELF)?;
+        separate_cycles += padded_cycles(&round);
+        rounds.push(round.journal.decode::<RoundCommit>()?);
+        round_state.apply_shot(*shot);
+    }
+
+    // Run the same init check and rounds in a single execution.
+    let input = OpeningInput {
+        state: state.clone(),
+        shots,
+        context: None,
+    };
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    let opening = default_executor().execute(env, OPENING_ELF)?;
+    let opening_cycles = padded_cycles(&opening);
+
+    assert_eq!(
+        opening.journal.decode::<OpeningCommit>()?,
+        OpeningCommit {
+            state: initial_state_commit,
+            rounds,
+        }
+    );
+
+    println!("separate proofs: {separate_cycles} cycles, opening proof: {opening_cycles} cycles");
+    assert!(opening_cycles < separate_cycles);
+
+    Ok(())
+}
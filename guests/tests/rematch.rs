@@ -0,0 +1,75 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{Direction, GameState, RematchCommit, RematchInput, Ship, ShipClass};
+use battleship_guests::REMATCH_ELF;
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+use common::example_state;
+
+fn execute_rematch(input: &RematchInput) -> anyhow::Result<RematchCommit> {
+    let env = ExecutorEnv::builder().write(input)?.build()?;
+    let execution = default_executor().execute(env, REMATCH_ELF)?;
+    Ok(execution.journal.decode()?)
+}
+
+#[test]
+fn reused_board() -> anyhow::Result<()> {
+    // The same layout with a fresh pepper is still flagged.
+    let prior_layout = example_state().layout_digest();
+    let state = GameState {
+        pepper: rand::random::<[u8; 32]>().to_vec(),
+        ..example_state()
+    };
+
+    let commit = execute_rematch(&RematchInput {
+        state: state.clone(),
+        prior_layout,
+    })?;
+    assert_eq!(commit.state, state.commit());
+    assert_eq!(commit.prior_layout, prior_layout);
+    assert!(!commit.is_fresh);
+
+    Ok(())
+}
+
+#[test]
+fn fresh_board() -> anyhow::Result<()> {
+    let prior_layout = example_state().layout_digest();
+    let mut state = example_state();
+    state.ships[4] = Ship::new(ShipClass::Destroyer, (0, 9), Direction::Horizontal);
+
+    let commit = execute_rematch(&RematchInput {
+        state: state.clone(),
+        prior_layout,
+    })?;
+    assert_eq!(commit.state, state.commit());
+    assert!(commit.is_fresh);
+
+    Ok(())
+}
+
+#[test]
+fn invalid_board() {
+    let mut state = example_state();
+    state.ships.pop();
+
+    let input = RematchInput {
+        state,
+        prior_layout: example_state().layout_digest(),
+    };
+    assert!(execute_rematch(&input).is_err());
+}
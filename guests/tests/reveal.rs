@@ -0,0 +1,51 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{Fleet, InitCommit, RevealCommit, RevealInput};
+use battleship_guests::{INIT_ELF, REVEAL_ELF};
+use risc0_zkvm::{default_executor, sha::Digest, ExecutorEnv};
+
+use common::example_state;
+
+#[test]
+fn reveal_matches_init_commit() -> anyhow::Result<()> {
+    let state = example_state();
+
+    let env = ExecutorEnv::builder()
+        .write(&state)?
+        .write(&Fleet::standard())?
+        .write(&None::<Digest>)?
+        .write(&None::<Digest>)?
+        .build()?;
+    let init: InitCommit = default_executor()
+        .execute(env, INIT_ELF)?
+        .journal
+        .decode()?;
+
+    let input = RevealInput {
+        state: state.clone(),
+        disclose_layout: true,
+    };
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    let reveal: RevealCommit = default_executor()
+        .execute(env, REVEAL_ELF)?
+        .journal
+        .decode()?;
+
+    assert_eq!(reveal.board_commit, init.state);
+    assert_eq!(reveal.layout, Some(state.layout_digest()));
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{Direction, GameState, MatchSetup, ShipClass};
+use battleship_guests::SHARED_OCEAN_ELF;
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+use common::example_state;
+
+fn execute_shared_ocean(state_a: &GameState, state_b: &GameState) -> anyhow::Result<MatchSetup> {
+    let env = ExecutorEnv::builder()
+        .write(state_a)?
+        .write(state_b)?
+        .build()?;
+    let execution = default_executor().execute(env, SHARED_OCEAN_ELF)?;
+    Ok(execution.journal.decode()?)
+}
+
+#[test]
+fn disjoint_boards() -> anyhow::Result<()> {
+    let state_a = example_state();
+    let state_b = GameState::builder()
+        .ship(ShipClass::Carrier, (0, 0), Direction::Horizontal)
+        .ship(ShipClass::Battleship, (8, 1), Direction::Vertical)
+        .ship(ShipClass::Cruiser, (5, 7), Direction::Vertical)
+        .ship(ShipClass::Submarine, (7, 9), Direction::Horizontal)
+        .ship(ShipClass::Destroyer, (0, 8), Direction::Horizontal)
+        .pepper(rand::random())
+        .build()?;
+
+    let setup = execute_shared_ocean(&state_a, &state_b)?;
+    assert_eq!(setup.player_a_commit, state_a.commit());
+    assert_eq!(setup.player_b_commit, state_b.commit());
+
+    Ok(())
+}
+
+#[test]
+fn overlapping_boards() {
+    let state_a = example_state();
+    // The destroyer overlaps with the submarine on the other board at (8, 5).
+    let state_b = GameState::builder()
+        .ship(ShipClass::Carrier, (0, 0), Direction::Horizontal)
+        .ship(ShipClass::Battleship, (8, 1), Direction::Vertical)
+        .ship(ShipClass::Cruiser, (5, 7), Direction::Vertical)
+        .ship(ShipClass::Submarine, (7, 9), Direction::Horizontal)
+        .ship(ShipClass::Destroyer, (8, 5), Direction::Vertical)
+        .pepper(rand::random())
+        .build()
+        .unwrap();
+
+    assert!(execute_shared_ocean(&state_a, &state_b).is_err());
+}
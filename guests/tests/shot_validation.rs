@@ -0,0 +1,72 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{GameState, OpeningInput, Position, RoundInput};
+use battleship_guests::{OPENING_ELF, ROUND_ELF};
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+use common::example_state;
+
+fn execute_round(state: &GameState, shot: Position, strict: bool) -> anyhow::Result<()> {
+    let input = RoundInput {
+        state: state.clone(),
+        shot,
+        strict,
+        context: None,
+    };
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    default_executor().execute(env, ROUND_ELF)?;
+    Ok(())
+}
+
+fn execute_opening(state: &GameState, shots: &[Position]) -> anyhow::Result<()> {
+    let input = OpeningInput {
+        state: state.clone(),
+        shots: shots.to_vec(),
+        context: None,
+    };
+    let env = ExecutorEnv::builder().write(&input)?.build()?;
+    default_executor().execute(env, OPENING_ELF)?;
+    Ok(())
+}
+
+#[test]
+fn out_of_bounds_shot() -> anyhow::Result<()> {
+    let state = example_state();
+    execute_round(&state, Position { x: 9, y: 9 }, false)?;
+    assert!(execute_round(&state, Position { x: 10, y: 9 }, false).is_err());
+    Ok(())
+}
+
+#[test]
+fn shot_at_sunk_ship() -> anyhow::Result<()> {
+    // The destroyer is sunk, so shooting at it again is only allowed under non-strict rules.
+    let mut state = example_state();
+    state.ships[4].hit_mask = 0x03;
+    execute_round(&state, Position { x: 7, y: 7 }, false)?;
+    assert!(execute_round(&state, Position { x: 7, y: 7 }, true).is_err());
+    execute_round(&state, Position { x: 7, y: 5 }, true)?;
+    Ok(())
+}
+
+#[test]
+fn repeated_opening_shot() -> anyhow::Result<()> {
+    let state = example_state();
+    let shots = [Position { x: 1, y: 1 }, Position { x: 2, y: 3 }];
+    execute_opening(&state, &shots)?;
+    assert!(execute_opening(&state, &[shots[0], shots[1], shots[0]]).is_err());
+    Ok(())
+}
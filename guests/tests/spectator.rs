@@ -0,0 +1,56 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use battleship_core::{Position, SpectatorCommit, SpectatorInput};
+use battleship_guests::SPECTATOR_ELF;
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+use common::{example_state, journal_bytes};
+
+#[test]
+fn spectator_journal_hides_ship_class() -> anyhow::Result<()> {
+    let mut state = example_state();
+
+    let mut hits = 0;
+    for (shot, is_hit) in [((7, 7), true), ((1, 1), false), ((8, 7), true)] {
+        let shot = Position::from(shot);
+        let input = SpectatorInput {
+            state: state.clone(),
+            shot,
+            hits,
+        };
+        let env = ExecutorEnv::builder().write(&input)?.build()?;
+        let execution = default_executor().execute(env, SPECTATOR_ELF)?;
+
+        let old_state = state.commit();
+        state.apply_shot(shot);
+        let expected = SpectatorCommit {
+            old_state,
+            new_state: state.commit(),
+            shot,
+            is_hit,
+            hits: hits + is_hit as u32,
+        };
+
+        // The journal contains exactly the spectator commit, which has no field for a ship class,
+        // so nothing about the class of the ship that was hit or sunk is disclosed.
+        let expected_journal = journal_bytes(&expected)?;
+        assert_eq!(execution.journal.bytes, expected_journal);
+        hits = expected.hits;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{Duration, Instant};
+
+use anyhow::ensure;
+use battleship_core::{Position, RoundCommit};
+use rand::seq::SliceRandom;
+
+use battleship_guests::verify::round_image_id;
+
+use crate::opponent::Opponent;
+
+#[derive(clap::Args)]
+pub struct BenchProveArgs {
+    /// Number of round proofs to generate.
+    #[arg(long, default_value_t = 10)]
+    rounds: u32,
+}
+
+// Total time taken to prove a number of rounds back-to-back.
+#[derive(Debug)]
+pub struct BenchReport {
+    pub rounds: u32,
+    pub total: Duration,
+}
+
+impl BenchReport {
+    pub fn proofs_per_minute(&self) -> f64 {
+        self.rounds as f64 * 60.0 / self.total.as_secs_f64()
+    }
+}
+
+// Prove the requested number of rounds with random shots against random boards, and report the
+// proving throughput.
+pub fn bench_prove(args: &BenchProveArgs) -> anyhow::Result<()> {
+    ensure!(args.rounds > 0, "at least one round is required");
+
+    let report = run_rounds(args.rounds)?;
+    println!(
+        "Proved {} rounds in {:.2?} ({:.1} proofs/minute)",
+        report.rounds,
+        report.total,
+        report.proofs_per_minute()
+    );
+    Ok(())
+}
+
+fn run_rounds(rounds: u32) -> anyhow::Result<BenchReport> {
+    let mut opponent = Opponent::new(rand::random());
+    let mut shots = shuffled_shots();
+    let mut total = Duration::ZERO;
+    for _ in 0..rounds {
+        // Each shot on a board is distinct, so the game ends before the shots run out. Start over
+        // on a new board when it does.
+        let shot = shots
+            .pop()
+            .expect("game should end before the shots run out");
+        let start = Instant::now();
+        let receipt = opponent.prove_apply_shot(shot)?;
+        total += start.elapsed();
+
+        // Verification is not timed, but checks that each proof is one the game would accept.
+        receipt.verify(round_image_id())?;
+        let round_commit: RoundCommit = receipt.journal.decode()?;
+        if round_commit.game_over {
+            opponent = Opponent::new(rand::random());
+            shots = shuffled_shots();
+        }
+    }
+    Ok(BenchReport { rounds, total })
+}
+
+fn shuffled_shots() -> Vec<Position> {
+    let mut shots: Vec<Position> = Position::all().collect();
+    shots.shuffle(&mut rand::rng());
+    shots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proofs_per_minute() {
+        let report = BenchReport {
+            rounds: 10,
+            total: Duration::from_secs(30),
+        };
+        assert_eq!(report.proofs_per_minute(), 20.0);
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::ensure;
+use battleship_core::GameState;
+use rand::{rngs::StdRng, SeedableRng};
+
+#[derive(clap::Args)]
+pub struct GenBoardArgs {
+    /// Seed for sampling the board, for reproducible boards. Uses a random seed if not given.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Write the board as JSON to the given file, instead of printing it.
+    #[arg(long, value_name = "PATH")]
+    out: Option<PathBuf>,
+}
+
+// Sample a random board, print it, and export it as JSON.
+pub fn gen_board(args: &GenBoardArgs) -> anyhow::Result<()> {
+    let state = sample_board(args.seed)?;
+    println!("{state}");
+
+    let json = serde_json::to_string_pretty(&state)?;
+    match &args.out {
+        Some(path) => {
+            fs::write(path, json)?;
+            println!("Board written to {}", path.display());
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+pub fn sample_board(seed: Option<u64>) -> anyhow::Result<GameState> {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+    let state: GameState = rand::Rng::random(&mut rng);
+    ensure!(state.check(), "sampled board is invalid");
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_board_reloads() -> anyhow::Result<()> {
+        let path = std::env::temp_dir().join(format!("board-{}.json", std::process::id()));
+        gen_board(&GenBoardArgs {
+            seed: Some(7),
+            out: Some(path.clone()),
+        })?;
+
+        let state: GameState = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        fs::remove_file(&path)?;
+        assert!(state.check());
+
+        // The same seed produces the same board.
+        assert_eq!(state, sample_board(Some(7))?);
+        Ok(())
+    }
+}
@@ -12,12 +12,128 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::ensure;
-use battleship_core::{GameState, HitType, Position, RoundCommit, RoundInput, ShipClass};
-use battleship_guests::{INIT_ELF, INIT_ID, ROUND_ELF, ROUND_ID};
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{ensure, Context};
+use battleship_core::{
+    GameConfig, GameError, GameState, HitType, MatchContext, OpeningCommit, Position, RoundCommit,
+    ShotReveal,
+};
+use battleship_guests::verify::{opening_image_id, round_image_id};
+use clap::{Parser, Subcommand};
 use inquire::Text;
 use regex::Regex;
-use risc0_zkvm::{default_prover, sha::Digest, ExecutorEnv, Receipt};
+use risc0_zkvm::sha::Digest;
+
+use crate::{
+    bench_prove::{bench_prove, BenchProveArgs},
+    gen_board::{gen_board, GenBoardArgs},
+    move_timer::MoveTimer,
+    opponent::Opponent,
+    place::{place, PlaceArgs},
+    replay::{replay, ReplayArgs},
+    retry::RetryPolicy,
+    round_log::RoundLog,
+    save::{Interrupt, SavedGame},
+    shot_script::ShotSource,
+    warmup::warmup,
+};
+
+mod bench_prove;
+mod gen_board;
+mod move_timer;
+mod opponent;
+mod place;
+mod replay;
+mod retry;
+mod round_log;
+mod save;
+mod shot_script;
+#[cfg(test)]
+mod test_utils;
+mod warmup;
+
+#[derive(Parser)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Prove each shot on a background thread while the next shot is entered.
+    #[arg(long)]
+    pipeline: bool,
+
+    /// Write a JSON object describing each round, one per line, to the given file.
+    #[arg(long, value_name = "PATH")]
+    log_jsonl: Option<PathBuf>,
+
+    /// Number of times to attempt each proof before giving up.
+    #[arg(long, default_value_t = 3)]
+    prove_attempts: u32,
+
+    /// Delay before retrying a failed proof, in milliseconds. Doubles after each failure.
+    #[arg(long, default_value_t = 1000, value_name = "MILLIS")]
+    prove_backoff: u64,
+
+    /// Play against the board in the given JSON file, instead of a random board.
+    #[arg(long, value_name = "PATH")]
+    board: Option<PathBuf>,
+
+    /// Read shots from the given file, one "x,y" per line, instead of prompting for them.
+    #[arg(long, value_name = "PATH")]
+    shots: Option<PathBuf>,
+
+    /// Execute the guests without generating proofs, using dev mode. Receipts are not secure.
+    #[arg(long, global = true)]
+    execute_only: bool,
+
+    /// Print the opponent's board before play, for practice. Only allowed with --execute-only,
+    /// since it reveals the board the proofs are meant to keep secret.
+    #[arg(long, requires = "execute_only")]
+    reveal: bool,
+
+    /// Commit to each shot after the opening before revealing it to the opponent, so the opponent
+    /// cannot choose how to respond based on the shot.
+    #[arg(long)]
+    commit_shots: bool,
+
+    /// Load the guests and print their image IDs before play, to check the expected versions.
+    #[arg(long)]
+    warmup: bool,
+
+    /// Where to save the progress of the game if it is interrupted with Ctrl-C.
+    #[arg(long, value_name = "PATH", default_value = "battleship-save.json")]
+    save: PathBuf,
+
+    /// Resume a game saved when it was interrupted. Requires the opponent's board with --board,
+    /// which must match the commitments in the save.
+    #[arg(long, value_name = "PATH", requires = "board")]
+    resume: Option<PathBuf>,
+
+    /// Require every proof to be bound to the match described in the given JSON file, with the
+    /// players, match ID, and timestamp agreed with the opponent.
+    #[arg(long, value_name = "PATH")]
+    match_context: Option<PathBuf>,
+
+    /// Print a grid of the hits and misses on the opponent's board after each shot.
+    #[arg(long)]
+    tracking_grid: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a random board and export it as JSON.
+    GenBoard(GenBoardArgs),
+    /// Place your own ships, preview the board, and prove that it is valid.
+    Place(PlaceArgs),
+    /// Re-prove the rounds recorded in a log against the opponent's board, and check the results.
+    Replay(ReplayArgs),
+    /// Prove random rounds back-to-back, and report the number of proofs per minute.
+    BenchProve(BenchProveArgs),
+}
 
 fn main() -> anyhow::Result<()> {
     // Initialize tracing. In order to view logs, run `RUST_LOG=info cargo run`
@@ -25,100 +141,232 @@ fn main() -> anyhow::Result<()> {
         .with_env_filter(tracing_subscriber::filter::EnvFilter::from_default_env())
         .init();
 
-    let mut opponent = Opponent::random();
+    let args = Args::parse();
 
-    // Require the opponent to prove that their board state is valid. Verify and store the commit.
-    println!("Opponent proving initial board state is valid");
-    let receipt = opponent.prove_init()?;
-    receipt.verify(INIT_ID)?;
-    let mut opponent_state_commit: Digest = receipt.journal.decode()?;
+    // Dev mode executes the guests and produces fake receipts, which only verify in dev mode.
+    if args.execute_only {
+        std::env::set_var("RISC0_DEV_MODE", "1");
+    }
 
-    // Run the game one round at a time, requiring the opponent to prove that the properly applied
-    // each of out shots to their private state.
-    let mut ship_classes = ShipClass::list().to_vec();
-    loop {
-        let shot = prompt_for_point()?;
+    match &args.command {
+        Some(Command::GenBoard(gen_args)) => return gen_board(gen_args),
+        Some(Command::Place(place_args)) => return place(place_args),
+        Some(Command::Replay(replay_args)) => return replay(replay_args),
+        Some(Command::BenchProve(bench_args)) => return bench_prove(bench_args),
+        None => {}
+    }
 
-        println!("Opponent proving application of shot {}", shot);
-        let receipt = opponent.prove_apply_shot(shot)?;
+    if args.warmup {
+        warmup()?;
+    }
 
-        receipt.verify(ROUND_ID)?;
-        let round_commit: RoundCommit = receipt.journal.decode()?;
+    let mut round_log = args
+        .log_jsonl
+        .as_ref()
+        .map(|path| File::create(path).map(RoundLog::new))
+        .transpose()?;
+    let opponent_state: GameState = match &args.board {
+        Some(path) => serde_json::from_str(&fs::read_to_string(path)?)?,
+        None => rand::random(),
+    };
+    if args.reveal {
+        println!("Opponent's board:\n{}", opponent_state);
+    }
+    let opponent = Opponent::new(opponent_state);
+    let mut shots = match &args.shots {
+        Some(path) => ShotSource::from_file(path)?,
+        None => ShotSource::Prompt,
+    };
+    let mut opponent = opponent.with_retry(RetryPolicy {
+        attempts: args.prove_attempts,
+        backoff: Duration::from_millis(args.prove_backoff),
+    });
+    let context = args
+        .match_context
+        .as_deref()
+        .map(load_match_context)
+        .transpose()?;
+    if let Some(context) = context {
+        opponent = opponent.with_match_context(context);
+    }
+    let interrupt = Interrupt::install()?;
+    let mut timer = MoveTimer::new();
 
-        // Check that the correct state and shot were used, then update our state commitment that
-        // binds the opponent to use the updated state.
-        ensure!(
-            opponent_state_commit == round_commit.old_state,
-            "opponent did not use the correct state"
-        );
-        ensure!(
-            shot == round_commit.shot,
-            "opponent did not use the correct shot"
-        );
-        opponent_state_commit = round_commit.new_state;
-
-        match round_commit.hit {
-            HitType::Miss => println!("Shot at {} is a miss", shot),
-            HitType::Hit => println!("You scored a hit at {}", shot),
-            HitType::Sunk(ship_class) => {
-                println!("You sunk a {:?} with your shot at {}", ship_class, shot);
-                if let Some(i) = ship_classes.iter().position(|c| ship_class == *c) {
-                    ship_classes.swap_remove(i);
-                };
+    let (mut saved, mut game_over) = match &args.resume {
+        // The opponent applies the saved shots to their board again. The chain of commitments
+        // resumes from the save, so the next round proof is rejected if the board does not match.
+        Some(path) => {
+            let saved = SavedGame::load(path)?;
+            ensure!(!saved.rounds.is_empty(), "saved game has no rounds");
+            opponent.apply_saved_shots(&saved.shots());
+            println!("Resuming the game after {} turns", saved.rounds.len());
+            (saved, false)
+        }
+        // Require the opponent to prove that their board state is valid, and to apply our first
+        // shot to it in the same proof. Verify and store the commit.
+        None => {
+            let shot = shots.next_shot()?.ok_or(GameError::ShotsExhausted)?;
+            println!(
+                "Opponent proving initial board state is valid and applying shot {}",
+                shot
+            );
+            let (receipt, elapsed) = timer.time(|| opponent.prove_opening(&[shot]));
+            let receipt = receipt?;
+            println!("Proof took {:.2?}", elapsed);
+            receipt.verify(opening_image_id())?;
+            let opening: OpeningCommit = receipt.journal.decode()?;
+            ensure!(
+                opening.rounds.len() == 1,
+                "opponent did not apply the opening shot"
+            );
+            let mut saved = SavedGame::new(opening.state);
+            let mut opponent_state_commit = opening.state;
+            opening.rounds[0].check_context(context.as_ref())?;
+            check_round(&mut opponent_state_commit, shot, &opening.rounds[0])?;
+            saved.record(&opening.rounds[0]);
+            if let Some(round_log) = round_log.as_mut() {
+                round_log.write(1, &opening.rounds[0])?;
             }
+            (saved, opening.rounds[0].game_over)
         }
+    };
+    let mut opponent_state_commit = saved.last_commit();
+    let mut fog = saved.fog();
+    // Show the grid after the opening shot, or the shots of a resumed game.
+    if args.tracking_grid {
+        println!("{fog}");
+    }
 
-        // If we've sunk each ship, the game is over.
-        if ship_classes.is_empty() {
-            break;
+    // Run the rest of the game one round at a time, requiring the opponent to prove that the
+    // properly applied each of out shots to their private state.
+    let config = GameConfig::default();
+    let mut turns = saved.rounds.len();
+    let mut next_shot = None;
+    while !game_over {
+        // Save between rounds when interrupted, so that no proven round is lost.
+        if interrupt.is_set() {
+            saved.save(&args.save)?;
+            println!(
+                "Game saved to {}. Resume it with --resume {0} --board <BOARD>",
+                args.save.display()
+            );
+            return Ok(());
+        }
+        if turns >= config.max_turns {
+            println!(
+                "You dealt {} damage to the opponent's fleet before the turn limit",
+                fog.damage()
+            );
+            print_move_times(&timer);
+            return Err(GameError::TurnLimitExceeded(config.max_turns).into());
         }
+        turns += 1;
+
+        let mut shot = match next_shot.take() {
+            Some(shot) => shot,
+            None => match shots.next_shot() {
+                Err(err) if interrupt.check_error(&err) => continue,
+                shot => shot?.ok_or(GameError::ShotsExhausted)?,
+            },
+        };
+        if args.commit_shots {
+            // Send the commitment first, and only reveal the shot once it is acknowledged. The
+            // round is then proven against the revealed shot.
+            let reveal = ShotReveal {
+                shot,
+                nonce: rand::random(),
+            };
+            opponent.accept_shot_commitment(reveal.digest());
+            shot = opponent.open_shot(&reveal)?;
+        }
+
+        println!("Opponent proving application of shot {}", shot);
+        let (receipt, elapsed) = match args.pipeline {
+            // Take the next shot while the proof is in progress. If this shot ends the game, the
+            // next shot is unused. Only the proof is timed, not the time taken to enter the shot.
+            true => {
+                let proof = opponent.prove_apply_shot_async(shot);
+                next_shot = match shots.next_shot() {
+                    Err(err) if interrupt.check_error(&err) => None,
+                    next_shot => next_shot?,
+                };
+                let (receipt, elapsed) = proof.join()?;
+                timer.record(elapsed);
+                (receipt, elapsed)
+            }
+            false => {
+                let (receipt, elapsed) = timer.time(|| opponent.prove_apply_shot(shot));
+                (receipt?, elapsed)
+            }
+        };
+        println!("Proof took {:.2?}", elapsed);
+
+        receipt.verify(round_image_id())?;
+        let round_commit: RoundCommit = receipt.journal.decode()?;
+        round_commit.check_context(context.as_ref())?;
+        check_round(&mut opponent_state_commit, shot, &round_commit)?;
+        fog.record_round(&round_commit);
+        if args.tracking_grid {
+            println!("{fog}");
+        }
+        saved.record(&round_commit);
+        if let Some(round_log) = round_log.as_mut() {
+            round_log.write(turns, &round_commit)?;
+        }
+        game_over = round_commit.game_over;
     }
 
+    // The opponent has proven that each ship is sunk, so the game is over.
     println!("You won!");
+    println!(
+        "You dealt {} damage to the opponent's fleet in {} turns",
+        fog.damage(),
+        turns
+    );
+    print_move_times(&timer);
     Ok(())
 }
 
-// An opponent with their secret Battleship board that the CLI user will play against.
-// This opponent is a stand-in for e.g. another human you'd play over the network.
-pub struct Opponent {
-    state: GameState,
+// Load the context of the match agreed with the opponent, returning the digest that proofs must
+// be bound to.
+fn load_match_context(path: &Path) -> anyhow::Result<Digest> {
+    let context: MatchContext = serde_json::from_str(&fs::read_to_string(path)?)
+        .with_context(|| format!("failed to parse the match context in {}", path.display()))?;
+    Ok(context.digest())
 }
 
-impl Opponent {
-    pub fn random() -> Self {
-        Self {
-            state: rand::random(),
-        }
-    }
-
-    // Produce a proof that the initial board state for the opponent is valid.
-    pub fn prove_init(&self) -> anyhow::Result<Receipt> {
-        let env = ExecutorEnv::builder().write(&self.state)?.build()?;
-        let prove_info = default_prover().prove(env, INIT_ELF).unwrap();
-
-        Ok(prove_info.receipt)
+fn print_move_times(timer: &MoveTimer) {
+    if let Some(summary) = timer.summary() {
+        println!(
+            "Proof times: min {:.2?}, max {:.2?}, mean {:.2?}",
+            summary.min, summary.max, summary.mean
+        );
     }
+}
 
-    // Apply the shot to the opponent's private state, and produce a proof for the update.
-    pub fn prove_apply_shot(&mut self, shot: Position) -> anyhow::Result<Receipt> {
-        let input = RoundInput {
-            state: self.state.clone(),
-            shot,
-        };
-        let env = ExecutorEnv::builder().write(&input)?.build()?;
-        let prove_info = default_prover().prove(env, ROUND_ELF).unwrap();
-
-        // Also update the state. This tracks the chain of states in the guest.
-        self.state.apply_shot(shot);
+// Check the round commit from the opponent and report the result.
+fn check_round(
+    opponent_state_commit: &mut Digest,
+    shot: Position,
+    round_commit: &RoundCommit,
+) -> anyhow::Result<()> {
+    // Check that the correct state and shot were used, then update our state commitment that
+    // binds the opponent to use the updated state.
+    *opponent_state_commit = round_commit.verify_transition(opponent_state_commit, shot)?;
 
-        Ok(prove_info.receipt)
+    match round_commit.hit {
+        HitType::Miss => println!("Shot at {} is a miss", shot),
+        HitType::Hit => println!("You scored a hit at {}", shot),
+        HitType::Sunk(ship_class) => {
+            println!("You sunk a {:?} with your shot at {}", ship_class, shot)
+        }
+        HitType::Invalid => println!("Shot at {} targets a ship that is already sunk", shot),
+        HitType::Deflected => println!("Shot at {} was deflected", shot),
     }
+    Ok(())
 }
 
 fn prompt_for_point() -> anyhow::Result<Position> {
-    // Create regex for validating coordinates in format "x,y" where x and y are 0-9
-    let coord_regex = Regex::new(r"^\(?([0-9]),\s*([0-9])\)?$").unwrap();
-
     loop {
         // Prompt the user for coordinates
         let input = Text::new(
@@ -127,18 +375,8 @@ fn prompt_for_point() -> anyhow::Result<Position> {
         .with_placeholder("x, y")
         .prompt()?;
 
-        // Try to parse and validate the input
-        if let Some(captures) = coord_regex.captures(input.trim()) {
-            // Extract x and y values
-            if let (Some(x_match), Some(y_match)) = (captures.get(1), captures.get(2)) {
-                let x: u32 = x_match.as_str().parse().unwrap(); // Safe to unwrap as regex ensures 0-9
-                let y: u32 = y_match.as_str().parse().unwrap();
-
-                // Additional validation (although regex already ensures 0-9)
-                if x <= 9 && y <= 9 {
-                    return Ok(Position { x, y });
-                }
-            }
+        if let Some(point) = parse_point(&input) {
+            return Ok(point);
         }
 
         // If we reach here, input was invalid
@@ -147,3 +385,18 @@ fn prompt_for_point() -> anyhow::Result<Position> {
         );
     }
 }
+
+// Parse a point entered by the user or read from a shot script.
+fn parse_point(input: &str) -> Option<Position> {
+    // Create regex for validating coordinates in format "x,y" where x and y are 0-9
+    let coord_regex = Regex::new(r"^\(?([0-9]),\s*([0-9])\)?$").unwrap();
+
+    // Try to parse and validate the input
+    let captures = coord_regex.captures(input.trim())?;
+    // Extract x and y values
+    let x: u32 = captures.get(1)?.as_str().parse().unwrap(); // Safe to unwrap as regex ensures 0-9
+    let y: u32 = captures.get(2)?.as_str().parse().unwrap();
+
+    // Additional validation (although regex already ensures 0-9)
+    Position::try_new(x, y).ok()
+}
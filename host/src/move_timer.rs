@@ -0,0 +1,106 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{Duration, Instant};
+
+// Records the wall-clock time of each proof in a game, to report the latency of each move.
+#[derive(Default)]
+pub struct MoveTimer {
+    durations: Vec<Duration>,
+}
+
+// Shortest, longest, and mean duration over the recorded moves.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MoveTimeSummary {
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+}
+
+impl MoveTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Run the operation, recording and returning the time it took along with its result.
+    pub fn time<T>(&mut self, op: impl FnOnce() -> T) -> (T, Duration) {
+        let start = Instant::now();
+        let value = op();
+        let elapsed = start.elapsed();
+        self.record(elapsed);
+        (value, elapsed)
+    }
+
+    // Record the duration of an operation timed elsewhere, e.g. on another thread.
+    pub fn record(&mut self, elapsed: Duration) {
+        self.durations.push(elapsed);
+    }
+
+    // Summarize the recorded durations, or return `None` if no moves were timed.
+    pub fn summary(&self) -> Option<MoveTimeSummary> {
+        let min = *self.durations.iter().min()?;
+        let max = *self.durations.iter().max()?;
+        let total: Duration = self.durations.iter().sum();
+        Some(MoveTimeSummary {
+            min,
+            max,
+            mean: total / self.durations.len() as u32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use battleship_core::{Position, RoundInput};
+    use battleship_guests::ROUND_ELF;
+    use risc0_zkvm::{default_executor, ExecutorEnv};
+
+    use super::*;
+
+    #[test]
+    fn records_execution_time() -> anyhow::Result<()> {
+        let input = RoundInput {
+            state: rand::random(),
+            shot: Position { x: 1, y: 1 },
+            strict: false,
+            context: None,
+        };
+        let mut timer = MoveTimer::new();
+        let (execution, elapsed) = timer.time(|| {
+            let env = ExecutorEnv::builder().write(&input)?.build()?;
+            default_executor().execute(env, ROUND_ELF)
+        });
+        execution?;
+
+        assert!(elapsed > Duration::ZERO);
+        assert_eq!(timer.durations, vec![elapsed]);
+        Ok(())
+    }
+
+    #[test]
+    fn summarize_durations() {
+        let mut timer = MoveTimer::new();
+        assert_eq!(timer.summary(), None);
+
+        timer.durations = [3, 1, 8].map(Duration::from_millis).to_vec();
+        assert_eq!(
+            timer.summary(),
+            Some(MoveTimeSummary {
+                min: Duration::from_millis(1),
+                max: Duration::from_millis(8),
+                mean: Duration::from_millis(4),
+            })
+        );
+    }
+}
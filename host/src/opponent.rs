@@ -0,0 +1,224 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use battleship_core::{
+    validate_shot_sequence, GameError, GameState, OpeningInput, Position, RoundInput, ShotReveal,
+};
+use battleship_guests::{OPENING_ELF, ROUND_ELF};
+use risc0_zkvm::{default_prover, sha::Digest, ExecutorEnv, Receipt};
+
+use crate::retry::RetryPolicy;
+
+// An opponent with their secret Battleship board that the CLI user will play against.
+// This opponent is a stand-in for e.g. another human you'd play over the network.
+pub struct Opponent {
+    state: GameState,
+    retry: RetryPolicy,
+    shot_commitment: Option<Digest>,
+    context: Option<Digest>,
+}
+
+impl Opponent {
+    pub fn new(state: GameState) -> Self {
+        Self {
+            state,
+            retry: RetryPolicy::default(),
+            shot_commitment: None,
+            context: None,
+        }
+    }
+
+    // Set the policy for retrying proofs that fail, e.g. due to network errors with a remote
+    // prover.
+    pub fn with_retry(self, retry: RetryPolicy) -> Self {
+        Self { retry, ..self }
+    }
+
+    // Bind each proof to the digest of the agreed match context, so that it cannot be reused in
+    // another match.
+    pub fn with_match_context(self, context: Digest) -> Self {
+        Self {
+            context: Some(context),
+            ..self
+        }
+    }
+
+    // Produce a proof that the initial board state for the opponent is valid, with the given
+    // opening shots applied to it.
+    pub fn prove_opening(&mut self, shots: &[Position]) -> anyhow::Result<Receipt> {
+        // Reject repeated or out of bounds shots before spending time on a proof the guest rejects.
+        validate_shot_sequence(shots)?;
+        let input = OpeningInput {
+            state: self.state.clone(),
+            shots: shots.to_vec(),
+            context: self.context,
+        };
+        let prove_info = self.retry.run(|| {
+            let env = ExecutorEnv::builder().write(&input)?.build()?;
+            default_prover().prove(env, OPENING_ELF)
+        })?;
+
+        // Also update the state. This tracks the chain of states in the guest.
+        self.state.apply_shots(shots);
+
+        Ok(prove_info.receipt)
+    }
+
+    // Apply the shots of a saved game without proving them again, since the proofs for them were
+    // already verified before the game was saved.
+    pub fn apply_saved_shots(&mut self, shots: &[Position]) {
+        self.state.apply_shots(shots);
+    }
+
+    // Acknowledge the shooter's commitment to their next shot, before it is revealed. In a real
+    // game the acknowledgement would be sent back to the shooter, who only then reveals the shot.
+    pub fn accept_shot_commitment(&mut self, commitment: Digest) {
+        self.shot_commitment = Some(commitment);
+    }
+
+    // Check the revealed shot against the commitment accepted for it, returning the shot to prove
+    // the round against. Each commitment can only be opened once.
+    pub fn open_shot(&mut self, reveal: &ShotReveal) -> Result<Position, GameError> {
+        let commitment = self
+            .shot_commitment
+            .take()
+            .ok_or(GameError::MissingShotCommitment)?;
+        reveal.open(&commitment)
+    }
+
+    // Apply the shot to the opponent's private state, and produce a proof for the update.
+    pub fn prove_apply_shot(&mut self, shot: Position) -> anyhow::Result<Receipt> {
+        let input = self.round_input(shot);
+        prove_round(&input, self.retry)
+    }
+
+    // Apply the shot to the opponent's private state, and start producing a proof for the update
+    // on a background thread. The state is updated immediately, so further shots can be applied
+    // while the proof is in progress.
+    pub fn prove_apply_shot_async(&mut self, shot: Position) -> ProveHandle {
+        let input = self.round_input(shot);
+        let retry = self.retry;
+        ProveHandle(thread::spawn(move || {
+            let start = Instant::now();
+            let receipt = prove_round(&input, retry)?;
+            Ok((receipt, start.elapsed()))
+        }))
+    }
+
+    fn round_input(&mut self, shot: Position) -> RoundInput {
+        let input = RoundInput {
+            state: self.state.clone(),
+            shot,
+            strict: false,
+            context: self.context,
+        };
+        tracing::info!("{}", input.witness_summary());
+
+        // Also update the state. This tracks the chain of states in the guest.
+        self.state.apply_shot(shot);
+
+        input
+    }
+}
+
+// Handle to a round proof running on a background thread.
+pub struct ProveHandle(JoinHandle<anyhow::Result<(Receipt, Duration)>>);
+
+impl ProveHandle {
+    // Wait for the proof to complete, returning the receipt and the time spent proving it, which
+    // excludes any time before the proof was waited on.
+    pub fn join(self) -> anyhow::Result<(Receipt, Duration)> {
+        self.0
+            .join()
+            .map_err(|_| anyhow!("proving thread panicked"))?
+    }
+}
+
+fn prove_round(input: &RoundInput, retry: RetryPolicy) -> anyhow::Result<Receipt> {
+    let prove_info = retry.run(|| {
+        let env = ExecutorEnv::builder().write(input)?.build()?;
+        default_prover().prove(env, ROUND_ELF)
+    })?;
+    Ok(prove_info.receipt)
+}
+
+#[cfg(test)]
+mod tests {
+    use battleship_core::RoundCommit;
+    use battleship_guests::ROUND_ID;
+
+    use super::*;
+
+    #[test]
+    fn mismatched_shot_reveal() {
+        let mut opponent = Opponent::new(rand::random());
+        let reveal = ShotReveal {
+            shot: Position { x: 1, y: 1 },
+            nonce: rand::random(),
+        };
+        assert_eq!(
+            opponent.open_shot(&reveal),
+            Err(GameError::MissingShotCommitment)
+        );
+
+        opponent.accept_shot_commitment(reveal.digest());
+        let changed = ShotReveal {
+            shot: Position { x: 2, y: 2 },
+            ..reveal.clone()
+        };
+        assert_eq!(
+            opponent.open_shot(&changed),
+            Err(GameError::ShotRevealMismatch)
+        );
+
+        opponent.accept_shot_commitment(reveal.digest());
+        assert_eq!(opponent.open_shot(&reveal), Ok(Position { x: 1, y: 1 }));
+    }
+
+    #[test]
+    fn prove_shots_concurrently() -> anyhow::Result<()> {
+        let mut opponent = Opponent::new(rand::random());
+        let initial_state_commit = opponent.state.commit();
+
+        // Start both proofs before waiting on either of them, and work out the result of each
+        // shot from the board to check the proven results against.
+        let (first_shot, second_shot) = (Position { x: 1, y: 1 }, Position { x: 2, y: 2 });
+        let first_expected = opponent.state.peek_shot(first_shot);
+        let first = opponent.prove_apply_shot_async(first_shot);
+        let second_expected = opponent.state.peek_shot(second_shot);
+        let second = opponent.prove_apply_shot_async(second_shot);
+
+        let (first, _) = first.join()?;
+        let (second, _) = second.join()?;
+        first.verify(ROUND_ID)?;
+        second.verify(ROUND_ID)?;
+
+        // The rounds should chain from the initial state to the current state.
+        let first: RoundCommit = first.journal.decode()?;
+        let second: RoundCommit = second.journal.decode()?;
+        assert_eq!(first.old_state, initial_state_commit);
+        assert_eq!(second.old_state, first.new_state);
+        assert_eq!(second.new_state, opponent.state.commit());
+        assert_eq!(first.hit, first_expected);
+        assert_eq!(second.hit, second_expected);
+
+        Ok(())
+    }
+}
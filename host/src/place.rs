@@ -0,0 +1,193 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::ensure;
+use battleship_core::{Direction, Fleet, GameState, InitCommit, Ship, ShipClass};
+use battleship_guests::{verify::init_image_id, INIT_ELF};
+use inquire::{Confirm, Select};
+use risc0_zkvm::{default_prover, sha::Digest, ExecutorEnv};
+
+use crate::{load_match_context, prompt_for_point};
+
+#[derive(clap::Args)]
+pub struct PlaceArgs {
+    /// Write the confirmed board as JSON to the given file.
+    #[arg(long, value_name = "PATH")]
+    out: Option<PathBuf>,
+
+    /// Commit the board for the match described in the given JSON file, as agreed with the
+    /// opponent.
+    #[arg(long, value_name = "PATH")]
+    match_context: Option<PathBuf>,
+}
+
+// Source of the ship placements and the final confirmation, so that the placement flow can be
+// driven by a script in tests.
+trait Placer {
+    fn place(&mut self, class: ShipClass) -> anyhow::Result<Ship>;
+    fn confirm(&mut self, state: &GameState) -> anyhow::Result<bool>;
+}
+
+// Prompts the user on the terminal.
+struct PromptPlacer;
+
+impl Placer for PromptPlacer {
+    fn place(&mut self, class: ShipClass) -> anyhow::Result<Ship> {
+        println!("Place your {:?} (length {})", class, class.span());
+        let pos = prompt_for_point()?;
+        let dir = match Select::new("Direction:", vec!["Horizontal", "Vertical"]).prompt()? {
+            "Horizontal" => Direction::Horizontal,
+            _ => Direction::Vertical,
+        };
+        Ok(Ship::new(class, pos, dir))
+    }
+
+    fn confirm(&mut self, state: &GameState) -> anyhow::Result<bool> {
+        println!("{state}");
+        Ok(Confirm::new("Use this board?")
+            .with_default(true)
+            .prompt()?)
+    }
+}
+
+// Place each ship of the fleet, preview the board, and prove that it is valid once confirmed.
+pub fn place(args: &PlaceArgs) -> anyhow::Result<()> {
+    let context = args
+        .match_context
+        .as_deref()
+        .map(load_match_context)
+        .transpose()?;
+    let (state, init_commit) =
+        place_and_prove(&mut PromptPlacer, |state| prove_init(state, context))?;
+    println!("Board committed as {}", init_commit.state);
+
+    if let Some(path) = &args.out {
+        fs::write(path, serde_json::to_string_pretty(&state)?)?;
+        println!("Board written to {}", path.display());
+    }
+    Ok(())
+}
+
+// Run the placement flow until the placer confirms a board, starting over each time a board is
+// rejected, and only then pass the board on to be proven.
+fn place_and_prove<T>(
+    placer: &mut impl Placer,
+    prove: impl FnOnce(&GameState) -> anyhow::Result<T>,
+) -> anyhow::Result<(GameState, T)> {
+    loop {
+        let mut state = GameState::new(rand::random());
+        for class in Fleet::standard().classes {
+            loop {
+                match state.try_add(placer.place(class)?) {
+                    Ok(()) => break,
+                    Err(err) => println!("Cannot place ship: {}", err),
+                }
+            }
+        }
+
+        if placer.confirm(&state)? {
+            let proven = prove(&state)?;
+            return Ok((state, proven));
+        }
+        println!("Board rejected, starting placement over");
+    }
+}
+
+fn prove_init(state: &GameState, context: Option<Digest>) -> anyhow::Result<InitCommit> {
+    println!("Proving initial board state is valid");
+    let env = ExecutorEnv::builder()
+        .write(state)?
+        .write(&Fleet::standard())?
+        .write(&None::<Digest>)?
+        .write(&context)?
+        .build()?;
+    let receipt = default_prover().prove(env, INIT_ELF)?.receipt;
+    receipt.verify(init_image_id())?;
+    let init_commit: InitCommit = receipt.journal.decode()?;
+    init_commit.check_fleet(&Fleet::standard())?;
+    init_commit.check_context(context.as_ref())?;
+    ensure!(
+        init_commit.state == state.commit(),
+        "init proof does not commit to the placed board"
+    );
+    Ok(init_commit)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    // Replays a fixed sequence of placements and confirmations.
+    struct ScriptedPlacer {
+        ships: VecDeque<Ship>,
+        confirmations: VecDeque<bool>,
+    }
+
+    impl Placer for ScriptedPlacer {
+        fn place(&mut self, class: ShipClass) -> anyhow::Result<Ship> {
+            let ship = self.ships.pop_front().unwrap();
+            assert_eq!(ship.class, class);
+            Ok(ship)
+        }
+
+        fn confirm(&mut self, _state: &GameState) -> anyhow::Result<bool> {
+            Ok(self.confirmations.pop_front().unwrap())
+        }
+    }
+
+    #[test]
+    fn rejected_board_restarts_placement() -> anyhow::Result<()> {
+        let rejected = [
+            Ship::new(ShipClass::Carrier, (0, 0), Direction::Horizontal),
+            Ship::new(ShipClass::Battleship, (0, 1), Direction::Horizontal),
+            Ship::new(ShipClass::Cruiser, (0, 2), Direction::Horizontal),
+            Ship::new(ShipClass::Submarine, (0, 3), Direction::Horizontal),
+            Ship::new(ShipClass::Destroyer, (0, 4), Direction::Horizontal),
+        ];
+        let confirmed = [
+            Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+            Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
+            Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
+            // Overlaps the cruiser, so the submarine is placed again.
+            Ship::new(ShipClass::Submarine, (3, 8), Direction::Horizontal),
+            Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+            Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+        ];
+        let mut placer = ScriptedPlacer {
+            ships: rejected.into_iter().chain(confirmed.clone()).collect(),
+            confirmations: [false, true].into(),
+        };
+
+        let mut proven = Vec::new();
+        let (state, ()) = place_and_prove(&mut placer, |state| {
+            proven.push(state.clone());
+            Ok(())
+        })?;
+
+        // Only the confirmed board is proven.
+        assert!(placer.ships.is_empty() && placer.confirmations.is_empty());
+        assert_eq!(proven, vec![state.clone()]);
+        let expected: Vec<Ship> = confirmed
+            .into_iter()
+            .filter(|s| s.pos != (3, 8).into())
+            .collect();
+        assert_eq!(state.ships, expected);
+        assert!(state.check());
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+use anyhow::{bail, ensure, Context};
+use battleship_core::{GameState, RoundCommit};
+use battleship_guests::verify::round_image_id;
+use clap::ArgGroup;
+
+use crate::{gen_board::sample_board, opponent::Opponent, round_log::RoundLogEntry};
+
+#[derive(clap::Args)]
+#[command(group(ArgGroup::new("opponent_board").required(true).args(["board", "seed"])))]
+pub struct ReplayArgs {
+    /// Log of the rounds to replay, as written with --log-jsonl.
+    log: PathBuf,
+
+    /// The opponent's board, revealed after the game, as JSON.
+    #[arg(long, value_name = "PATH")]
+    board: Option<PathBuf>,
+
+    /// Seed that the opponent's board was generated from with gen-board.
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+// Re-prove each round in the log against the opponent's board, and check that the proven results
+// match the log.
+pub fn replay(args: &ReplayArgs) -> anyhow::Result<()> {
+    let state: GameState = match (&args.board, args.seed) {
+        (Some(path), _) => serde_json::from_str(&fs::read_to_string(path)?)?,
+        (None, Some(seed)) => sample_board(Some(seed))?,
+        (None, None) => bail!("either a board or a seed is required"),
+    };
+    let entries = read_log(BufReader::new(File::open(&args.log)?))?;
+
+    let mismatches = replay_entries(state, &entries)?;
+    ensure!(
+        mismatches == 0,
+        "{} of {} rounds did not match the log",
+        mismatches,
+        entries.len()
+    );
+    println!("All {} rounds matched the log", entries.len());
+    Ok(())
+}
+
+fn read_log(reader: impl BufRead) -> anyhow::Result<Vec<RoundLogEntry>> {
+    let mut entries = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line)
+            .with_context(|| format!("failed to parse line {} of the log", i + 1))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+// Prove each logged shot in order, printing a diff for each round where the proven result differs
+// from the log. Returns the number of rounds that differ.
+fn replay_entries(state: GameState, entries: &[RoundLogEntry]) -> anyhow::Result<usize> {
+    let mut opponent = Opponent::new(state);
+    let mut mismatches = 0;
+    for entry in entries {
+        let receipt = opponent.prove_apply_shot(entry.shot)?;
+        receipt.verify(round_image_id())?;
+        let round_commit: RoundCommit = receipt.journal.decode()?;
+        if let Some(diff) = diff_entries(entry, &RoundLogEntry::new(entry.turn, &round_commit)) {
+            println!("{}", diff);
+            mismatches += 1;
+        }
+    }
+    Ok(mismatches)
+}
+
+fn diff_entries(logged: &RoundLogEntry, proven: &RoundLogEntry) -> Option<String> {
+    if logged == proven {
+        return None;
+    }
+    let mut diff = format!("turn {}, shot {}:", logged.turn, logged.shot);
+    if logged.hit != proven.hit {
+        diff += &format!("\n- hit: {:?}\n+ hit: {:?}", logged.hit, proven.hit);
+    }
+    if logged.old_commit != proven.old_commit {
+        diff += &format!(
+            "\n- old_commit: {}\n+ old_commit: {}",
+            logged.old_commit, proven.old_commit
+        );
+    }
+    if logged.new_commit != proven.new_commit {
+        diff += &format!(
+            "\n- new_commit: {}\n+ new_commit: {}",
+            logged.new_commit, proven.new_commit
+        );
+    }
+    Some(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use battleship_core::HitType;
+
+    use super::*;
+    use crate::{round_log::RoundLog, test_utils::example_state};
+
+    fn recorded_game() -> anyhow::Result<(GameState, Vec<RoundLogEntry>)> {
+        let state = example_state();
+        let mut played = state.clone();
+        let mut buf = Vec::new();
+        let mut log = RoundLog::new(&mut buf);
+        for (i, shot) in [(1, 1), (7, 7), (8, 7)].into_iter().enumerate() {
+            log.write(i + 1, &played.play_round(shot.into())?)?;
+        }
+        let entries = read_log(buf.as_slice())?;
+        Ok((state, entries))
+    }
+
+    #[test]
+    fn replay_recorded_game() -> anyhow::Result<()> {
+        let (state, entries) = recorded_game()?;
+        assert_eq!(entries.len(), 3);
+        assert_eq!(replay_entries(state, &entries)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn diff_mismatched_hit() -> anyhow::Result<()> {
+        let (_, entries) = recorded_game()?;
+        let mut tampered = entries[1].clone();
+        tampered.hit = HitType::Miss;
+
+        assert_eq!(diff_entries(&entries[1], &entries[1]), None);
+        let diff = diff_entries(&tampered, &entries[1]).unwrap();
+        assert_eq!(diff, "turn 2, shot (7, 7):\n- hit: Miss\n+ hit: Hit");
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{thread, time::Duration};
+
+use anyhow::Context;
+
+// Policy for retrying an operation that may fail transiently, such as proving with a remote
+// prover. The delay between attempts doubles after each failure.
+#[derive(Copy, Clone, Debug)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RetryPolicy {
+    // Run the operation until it succeeds, or return the last error once all attempts are used.
+    pub fn run<T>(&self, mut op: impl FnMut() -> anyhow::Result<T>) -> anyhow::Result<T> {
+        let mut backoff = self.backoff;
+        let mut attempt = 1;
+        loop {
+            match op() {
+                Ok(value) => return Ok(value),
+                Err(err) if attempt < self.attempts => {
+                    tracing::warn!("attempt {attempt} of {} failed: {err:#}", self.attempts);
+                    thread::sleep(backoff);
+                    backoff = backoff.saturating_mul(2);
+                    attempt += 1;
+                }
+                Err(err) => {
+                    return Err(err).with_context(|| format!("failed after {attempt} attempts"))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::bail;
+
+    use super::*;
+
+    // A stand-in for a remote prover that fails a fixed number of times before succeeding.
+    struct MockProver {
+        failures: u32,
+        calls: u32,
+    }
+
+    impl MockProver {
+        fn prove(&mut self) -> anyhow::Result<&'static str> {
+            self.calls += 1;
+            if self.calls <= self.failures {
+                bail!("connection reset");
+            }
+            Ok("receipt")
+        }
+    }
+
+    #[test]
+    fn retry_until_success() -> anyhow::Result<()> {
+        let policy = RetryPolicy {
+            attempts: 3,
+            backoff: Duration::from_millis(1),
+        };
+        let mut prover = MockProver {
+            failures: 2,
+            calls: 0,
+        };
+        assert_eq!(policy.run(|| prover.prove())?, "receipt");
+        assert_eq!(prover.calls, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn retry_exhausted() {
+        let policy = RetryPolicy {
+            attempts: 2,
+            backoff: Duration::from_millis(1),
+        };
+        let mut prover = MockProver {
+            failures: 2,
+            calls: 0,
+        };
+        let err = policy.run(|| prover.prove()).unwrap_err();
+        assert_eq!(prover.calls, 2);
+        assert_eq!(err.root_cause().to_string(), "connection reset");
+    }
+}
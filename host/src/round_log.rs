@@ -0,0 +1,101 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use battleship_core::{HitType, Position, RoundCommit};
+use serde::{Deserialize, Serialize};
+
+// A machine-readable record of one round, written as a line of JSON.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RoundLogEntry {
+    pub turn: usize,
+    pub shot: Position,
+    #[serde(with = "battleship_core::hit_type_json")]
+    pub hit: HitType,
+    // Hex-encoded commitment to the opponent's state before the shot.
+    pub old_commit: String,
+    // Hex-encoded commitment to the opponent's state after the shot.
+    pub new_commit: String,
+}
+
+impl RoundLogEntry {
+    pub fn new(turn: usize, round_commit: &RoundCommit) -> Self {
+        Self {
+            turn,
+            shot: round_commit.shot.into(),
+            hit: round_commit.hit.clone(),
+            old_commit: round_commit.old_state.to_string(),
+            new_commit: round_commit.new_state.to_string(),
+        }
+    }
+}
+
+// Writes one JSON object per round, for consumption by external tooling.
+pub struct RoundLog<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> RoundLog<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn write(&mut self, turn: usize, round_commit: &RoundCommit) -> anyhow::Result<()> {
+        serde_json::to_writer(&mut self.writer, &RoundLogEntry::new(turn, round_commit))?;
+        writeln!(self.writer)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use battleship_core::ShipClass;
+
+    use super::*;
+    use crate::test_utils::example_state;
+
+    #[test]
+    fn log_lines_parse() -> anyhow::Result<()> {
+        let mut state = example_state();
+        let round_commits: Vec<RoundCommit> = [(1, 1), (7, 7), (8, 7)]
+            .into_iter()
+            .map(|shot| state.play_round(shot.into()).unwrap())
+            .collect();
+
+        let mut log = RoundLog::new(Vec::new());
+        for (i, round_commit) in round_commits.iter().enumerate() {
+            log.write(i + 1, round_commit)?;
+        }
+
+        let output = String::from_utf8(log.writer)?;
+        let entries = output
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<RoundLogEntry>, _>>()?;
+        assert_eq!(entries.len(), 3);
+        for (i, (entry, round_commit)) in entries.iter().zip(&round_commits).enumerate() {
+            assert_eq!(*entry, RoundLogEntry::new(i + 1, round_commit));
+        }
+        assert_eq!(entries[2].hit, HitType::Sunk(ShipClass::Destroyer));
+        assert_eq!(
+            entries[0].old_commit,
+            round_commits[0].old_state.to_string()
+        );
+        assert_eq!(entries[0].old_commit.len(), 64);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::Context;
+use battleship_core::{validate_shot_sequence, FogBoard, HitType, Position, RoundCommit};
+use inquire::InquireError;
+use risc0_zkvm::sha::Digest;
+use serde::{Deserialize, Serialize};
+use signal_hook::consts::SIGINT;
+
+// Progress through a game, saved when the game is interrupted so that it can be resumed. Holds the
+// chain of commitments to the opponent's board and the shots that were proven against it.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SavedGame {
+    // Commitment to the opponent's board before the first shot.
+    pub initial_commit: Digest,
+    pub rounds: Vec<SavedRound>,
+}
+
+// A proven round, with the commitment to the opponent's board after the shot.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SavedRound {
+    pub shot: Position,
+    #[serde(with = "battleship_core::hit_type_json")]
+    pub hit: HitType,
+    // Cells of the ship sunk by the shot, if any. Missing in games saved by older versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunk_cells: Option<Vec<Position>>,
+    pub commit: Digest,
+}
+
+impl SavedGame {
+    pub fn new(initial_commit: Digest) -> Self {
+        Self {
+            initial_commit,
+            rounds: Vec::new(),
+        }
+    }
+
+    // Record a round that has been verified to continue from the last commitment.
+    pub fn record(&mut self, round_commit: &RoundCommit) {
+        self.rounds.push(SavedRound {
+            shot: round_commit.shot.into(),
+            hit: round_commit.hit.clone(),
+            sunk_cells: round_commit.sunk_cells.clone(),
+            commit: round_commit.new_state,
+        });
+    }
+
+    // Commitment to the opponent's board after the last proven round, which the next round must
+    // continue from.
+    pub fn last_commit(&self) -> Digest {
+        self.rounds
+            .last()
+            .map_or(self.initial_commit, |round| round.commit)
+    }
+
+    pub fn shots(&self) -> Vec<Position> {
+        self.rounds.iter().map(|round| round.shot).collect()
+    }
+
+    // Rebuild what is known about the opponent's board from the results of the saved rounds.
+    pub fn fog(&self) -> FogBoard {
+        let mut fog = FogBoard::new();
+        for round in self.rounds.iter() {
+            fog.record(round.shot, &round.hit);
+            if let (HitType::Sunk(class), Some(cells)) = (&round.hit, &round.sunk_cells) {
+                fog.record_sunk(*class, cells);
+            }
+        }
+        fog
+    }
+
+    // Save the game as JSON, compressed with gzip when built with the `compress` feature.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let json = serde_json::to_vec_pretty(self)?;
+        fs::write(path, compress(json)?)
+            .with_context(|| format!("failed to save the game to {}", path.display()))
+    }
+
+    // Load a saved game, rejecting saves with repeated or out of bounds shots, which no proven
+    // game can contain. Compressed saves are detected by the gzip header and decompressed.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let mut bytes = fs::read(path)?;
+        if bytes.starts_with(&GZIP_MAGIC) {
+            bytes = decompress(&bytes).with_context(|| {
+                format!("failed to decompress the saved game in {}", path.display())
+            })?;
+        }
+        let saved: Self = serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed to parse the saved game in {}", path.display()))?;
+        validate_shot_sequence(&saved.shots())?;
+        Ok(saved)
+    }
+}
+
+// The first two bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[cfg(feature = "compress")]
+fn compress(bytes: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&bytes)?;
+    Ok(encoder.finish()?)
+}
+
+#[cfg(not(feature = "compress"))]
+fn compress(bytes: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    Ok(bytes)
+}
+
+#[cfg(feature = "compress")]
+fn decompress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut decompressed = Vec::new();
+    flate2::read::GzDecoder::new(bytes).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+// Without the feature, compressed saves are rejected up front, rather than with a confusing parse
+// error.
+#[cfg(not(feature = "compress"))]
+fn decompress(_bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+    anyhow::bail!("the save is gzip-compressed; rebuild with the compress feature to load it")
+}
+
+// Set when the user presses Ctrl-C, so that an interrupted game is saved between rounds, rather
+// than the process exiting in the middle of a proof.
+#[derive(Clone)]
+pub struct Interrupt(Arc<AtomicBool>);
+
+impl Interrupt {
+    // Handle SIGINT by setting the flag instead of exiting. A second SIGINT exits immediately, for
+    // when the user does not want to wait for a proof to finish.
+    pub fn install() -> anyhow::Result<Self> {
+        let flag = Arc::new(AtomicBool::new(false));
+        signal_hook::flag::register_conditional_shutdown(SIGINT, 130, flag.clone())?;
+        signal_hook::flag::register(SIGINT, flag.clone())?;
+        Ok(Self(flag))
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    pub fn set(&self) {
+        self.0.store(true, Ordering::SeqCst)
+    }
+
+    // Prompts read the terminal in raw mode, where Ctrl-C does not raise SIGINT, and instead
+    // return an error. Records the interrupt and returns true if the error is from Ctrl-C.
+    pub fn check_error(&self, err: &anyhow::Error) -> bool {
+        let interrupted = matches!(
+            err.downcast_ref::<InquireError>(),
+            Some(InquireError::OperationInterrupted)
+        );
+        if interrupted {
+            self.set();
+        }
+        interrupted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use battleship_core::{Knowledge, ShipClass};
+
+    use super::*;
+    use crate::test_utils::example_state;
+
+    #[test]
+    fn interrupted_game_reloads() -> anyhow::Result<()> {
+        let mut state = example_state();
+        let initial_state = state.clone();
+        let interrupt = Interrupt::install()?;
+
+        // Play rounds until the process is interrupted part way through the game.
+        let mut saved = SavedGame::new(state.commit());
+        for (i, shot) in [(1, 1), (7, 7), (8, 7), (2, 5)].into_iter().enumerate() {
+            if interrupt.is_set() {
+                break;
+            }
+            saved.record(&state.play_round(shot.into()).unwrap());
+            if i == 2 {
+                signal_hook::low_level::raise(SIGINT)?;
+            }
+        }
+        assert!(interrupt.is_set());
+        assert_eq!(saved.rounds.len(), 3);
+
+        let path =
+            std::env::temp_dir().join(format!("interrupted-game-{}.json", std::process::id()));
+        saved.save(&path)?;
+        let loaded = SavedGame::load(&path)?;
+        fs::remove_file(&path)?;
+        assert_eq!(loaded, saved);
+
+        // Replaying the saved shots on the board leads to the last saved commitment, so the next
+        // round can continue the chain.
+        assert_eq!(
+            initial_state.apply_shots_pure(&loaded.shots()).commit(),
+            loaded.last_commit()
+        );
+        assert_eq!(loaded.fog().damage(), 2);
+        // The destroyer was sunk by the last saved round, which marks the cell hit before it too.
+        assert_eq!(
+            loaded.fog().get(Position { x: 7, y: 7 }),
+            Some(Knowledge::Sunk(ShipClass::Destroyer))
+        );
+        Ok(())
+    }
+
+    #[cfg(not(feature = "compress"))]
+    #[test]
+    fn compressed_save_rejected() -> anyhow::Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("rejected-save-{}.json.gz", std::process::id()));
+        fs::write(&path, [0x1f, 0x8b, 0x08, 0x00])?;
+        let err = SavedGame::load(&path).unwrap_err();
+        fs::remove_file(&path)?;
+        assert!(format!("{err:#}").contains("gzip-compressed"), "{err:#}");
+        Ok(())
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn compressed_save_round_trip() -> anyhow::Result<()> {
+        let mut state = example_state();
+        let mut saved = SavedGame::new(state.commit());
+        for shot in [(1, 1), (7, 7), (8, 7)] {
+            saved.record(&state.play_round(shot.into())?);
+        }
+
+        let path =
+            std::env::temp_dir().join(format!("round-trip-save-{}.json.gz", std::process::id()));
+        saved.save(&path)?;
+        let bytes = fs::read(&path)?;
+        let loaded = SavedGame::load(&path)?;
+        fs::remove_file(&path)?;
+        assert!(bytes.starts_with(&GZIP_MAGIC));
+        assert_eq!(loaded, saved);
+
+        // Saves written as plain JSON, e.g. by builds without the feature, still load.
+        let path =
+            std::env::temp_dir().join(format!("round-trip-save-{}.json", std::process::id()));
+        fs::write(&path, serde_json::to_vec_pretty(&saved)?)?;
+        let loaded = SavedGame::load(&path)?;
+        fs::remove_file(&path)?;
+        assert_eq!(loaded, saved);
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::Path, vec};
+
+use anyhow::anyhow;
+use battleship_core::Position;
+
+use crate::{parse_point, prompt_for_point};
+
+// Where the shots for a game come from: the user, or a script for playing without prompts.
+pub enum ShotSource {
+    Prompt,
+    Script(vec::IntoIter<Position>),
+}
+
+impl ShotSource {
+    // Load a script of shots from a file, with one "x,y" shot per line. Blank lines are skipped.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let shots = fs::read_to_string(path)?
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                parse_point(line)
+                    .ok_or_else(|| anyhow!("invalid shot on line {} of the script", i + 1))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self::Script(shots.into_iter()))
+    }
+
+    // Get the next shot, or `None` if the script has run out.
+    pub fn next_shot(&mut self) -> anyhow::Result<Option<Position>> {
+        match self {
+            Self::Prompt => prompt_for_point().map(Some),
+            Self::Script(shots) => Ok(shots.next()),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use battleship_guests::{INIT_ELF, OPENING_ELF, ROUND_ELF};
+use risc0_zkvm::{compute_image_id, sha::Digest};
+
+// The guests used in a game, by name, in the order they are first proven.
+const GAME_GUESTS: [(&str, &[u8]); 3] = [
+    ("init", INIT_ELF),
+    ("opening", OPENING_ELF),
+    ("round", ROUND_ELF),
+];
+
+// Load each guest used in a game and compute its image ID from the ELF, so that any problem with
+// the guests is found before play starts, rather than at the first proof.
+pub fn image_ids() -> anyhow::Result<Vec<(&'static str, Digest)>> {
+    GAME_GUESTS
+        .iter()
+        .map(|&(name, elf)| Ok((name, compute_image_id(elf)?)))
+        .collect()
+}
+
+// Print the image ID of each guest, so that users can pin the versions they expect to play with.
+pub fn warmup() -> anyhow::Result<()> {
+    for (name, image_id) in image_ids()? {
+        println!("{} image ID: {}", name, image_id);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use battleship_guests::verify::{init_image_id, opening_image_id, round_image_id};
+
+    #[test]
+    fn image_ids_match_guests() -> anyhow::Result<()> {
+        let expected = [
+            ("init", init_image_id()),
+            ("opening", opening_image_id()),
+            ("round", round_image_id()),
+        ];
+        assert_eq!(image_ids()?, expected);
+        Ok(())
+    }
+}
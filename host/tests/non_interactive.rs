@@ -0,0 +1,152 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    fs,
+    process::{Command, Output},
+};
+
+use battleship_core::{Direction, GameState, Ship, ShipClass};
+
+fn example_state() -> GameState {
+    GameState {
+        ships: vec![
+            Ship::new(ShipClass::Carrier, (2, 3), Direction::Vertical),
+            Ship::new(ShipClass::Battleship, (3, 1), Direction::Horizontal),
+            Ship::new(ShipClass::Cruiser, (4, 7), Direction::Vertical),
+            Ship::new(ShipClass::Submarine, (7, 5), Direction::Horizontal),
+            Ship::new(ShipClass::Destroyer, (7, 7), Direction::Horizontal),
+        ],
+        ..GameState::new(rand::random())
+    }
+}
+
+// Run the host against the given board and shot script, with the given extra arguments.
+fn run_scripted(
+    name: &str,
+    state: &GameState,
+    shots: &str,
+    args: &[&str],
+) -> anyhow::Result<Output> {
+    let dir = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let board_path = dir.join("board.json");
+    let shots_path = dir.join("shots.txt");
+    fs::write(&board_path, serde_json::to_string(state)?)?;
+    fs::write(&shots_path, shots)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_host"))
+        .arg("--board")
+        .arg(&board_path)
+        .arg("--shots")
+        .arg(&shots_path)
+        .args(args)
+        .output()?;
+    fs::remove_dir_all(&dir)?;
+    Ok(output)
+}
+
+#[test]
+fn scripted_example_game() -> anyhow::Result<()> {
+    let state = example_state();
+    // The shots from the example_game guest test, which sink every ship.
+    let shots = "\
+        1,1\n2,5\n3,5\n2,6\n2,7\n2,8\n2,4\n2,3\n\
+        4,9\n4,8\n4,7\n\
+        7,2\n7,7\n6,7\n8,7\n\
+        8,5\n7,5\n9,5\n\
+        3,1\n4,1\n5,1\n6,1\n";
+
+    let output = run_scripted("scripted-game", &state, shots, &["--execute-only"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(
+        output.status.success(),
+        "host failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("You won!"));
+    assert!(stdout.contains("in 22 turns"));
+    Ok(())
+}
+
+#[test]
+fn game_ends_on_final_sink() -> anyhow::Result<()> {
+    let state = example_state();
+    // Sink every ship but the battleship, then sink it with the fourth of these shots.
+    let shots = "\
+        2,3\n2,4\n2,5\n2,6\n2,7\n\
+        4,7\n4,8\n4,9\n\
+        7,5\n8,5\n9,5\n\
+        7,7\n8,7\n\
+        3,1\n4,1\n5,1\n";
+
+    // Without the final shot, the game does not end.
+    let output = run_scripted("final-sink-short", &state, shots, &["--execute-only"])?;
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(!stdout.contains("You won!"));
+    assert!(String::from_utf8(output.stderr)?.contains("shots ran out"));
+
+    // With it, the game ends on that shot and any further shots are never taken.
+    let shots = format!("{}6,1\n0,0\n", shots);
+    let output = run_scripted("final-sink", &state, &shots, &["--execute-only"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(
+        output.status.success(),
+        "host failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("You sunk a Battleship with your shot at (6, 1)\nYou won!"));
+    assert!(stdout.contains("in 17 turns"));
+    assert!(!stdout.contains("(0, 0)"));
+    Ok(())
+}
+
+#[test]
+fn reveal_prints_board() -> anyhow::Result<()> {
+    let state = example_state();
+    let output = run_scripted("reveal", &state, "1,1\n", &["--execute-only", "--reveal"])?;
+
+    // The board is printed before any proof, so this does not depend on how the game ends.
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.starts_with(&format!("Opponent's board:\n{}", state)));
+    Ok(())
+}
+
+#[test]
+fn reveal_requires_execute_only() -> anyhow::Result<()> {
+    let state = example_state();
+    let output = run_scripted("reveal-proving", &state, "1,1\n", &["--reveal"])?;
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("--execute-only"));
+    assert!(!String::from_utf8(output.stdout)?.contains("Opponent's board"));
+    Ok(())
+}
+
+#[test]
+fn bench_prove_execute_only() -> anyhow::Result<()> {
+    // The top-level --execute-only flag also applies to the subcommand, and runs in the child
+    // process rather than setting dev mode for the other tests.
+    let output = Command::new(env!("CARGO_BIN_EXE_host"))
+        .args(["bench-prove", "--rounds", "1", "--execute-only"])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "host failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8(output.stdout)?.starts_with("Proved 1 rounds"));
+    Ok(())
+}